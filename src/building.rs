@@ -2,6 +2,7 @@ mod grid;
 mod placeable;
 mod extraction_beacon;
 mod build_mode;
+mod turret;
 
 pub use extraction_beacon::*;
 pub use placeable::*;
@@ -9,6 +10,7 @@ pub use grid::*;
 pub use build_mode::*;
 
 use bevy::prelude::*;
+use crate::physics::SimulationSet;
 use crate::state::GameState;
 
 pub struct BuildingPlugin;
@@ -24,9 +26,19 @@ impl Plugin for BuildingPlugin {
                 build_mode::update_grid_overlay_position,
                 build_mode::update_ghost_preview,
             ).run_if(in_state(GameState::Playing)))
-            .add_systems(Update, (
-                build_mode::place_structure,
+            .add_systems(Update, build_mode::place_structure.run_if(in_state(GameState::Playing)))
+            // Beacon defense: charge pausing, escalating drone spawns and charge bar
+            .add_systems(FixedUpdate, (
+                turret::turret_shoot,
+                extraction_beacon::beacon_defense_spawns.run_if(crate::spawning::under_live_drone_cap),
+            ).in_set(SimulationSet::Intent).run_if(in_state(GameState::Playing)))
+            .add_systems(FixedUpdate, (
+                extraction_beacon::detect_beacon_damage,
                 extraction_beacon::tick_beacon_charge,
+            ).chain().in_set(SimulationSet::Damage).run_if(in_state(GameState::Playing)))
+            .add_systems(Update, (
+                extraction_beacon::attach_beacon_charge_bar,
+                extraction_beacon::update_beacon_charge_bar,
             ).run_if(in_state(GameState::Playing)));
     }
 }
\ No newline at end of file
@@ -1,8 +1,8 @@
 use bevy::prelude::*;
 use bevy::input::mouse::MouseWheel;
-use bevy::window::PrimaryWindow;
 
-use crate::camera::GameCamera;
+use crate::camera::CursorWorld;
+use crate::difficulty::Difficulty;
 use crate::inventory::{Inventory, first_available_placeable, remove_placeable, has_placeable};
 use crate::player::Player;
 use super::grid::{BuildGrid, world_to_grid, grid_to_world, GRID_CELL_SIZE};
@@ -179,22 +179,16 @@ pub fn update_grid_overlay_position(
 pub fn update_ghost_preview(
     build_mode: Res<BuildMode>,
     build_grid: Res<BuildGrid>,
-    window: Query<&Window, With<PrimaryWindow>>,
-    camera: Query<(&Camera, &GlobalTransform), With<GameCamera>>,
+    cursor_world: Res<CursorWorld>,
     mut ghost_query: Query<(&mut Transform, &mut TextColor), With<GhostPreview>>,
 ) {
     if build_mode.selected.is_none() {
         return;
     }
 
-    let Ok(window) = window.single() else { return };
-    let Ok((cam, cam_transform)) = camera.single() else { return };
     let Ok((mut ghost_transform, mut text_color)) = ghost_query.single_mut() else { return };
 
-    let Some(cursor_world) = window.cursor_position()
-        .and_then(|cursor| cam.viewport_to_world(cam_transform, cursor).ok())
-        .map(|ray| ray.origin.truncate())
-    else { return };
+    let Some(cursor_world) = cursor_world.0 else { return };
 
     let grid_pos = world_to_grid(cursor_world);
     let snapped = grid_to_world(grid_pos);
@@ -209,14 +203,32 @@ pub fn update_ghost_preview(
     }
 }
 
+/// Spawns the structure in the grid cell under `world_pos` and claims the cell.
+/// Returns the cell, or None (and spawns nothing) if it's already taken
+pub fn place_in_grid(
+    commands: &mut Commands,
+    build_grid: &mut BuildGrid,
+    world_pos: Vec2,
+    config: &PlaceableConfig,
+    difficulty: &Difficulty,
+) -> Option<(i32, i32)> {
+    let grid_pos = world_to_grid(world_pos);
+    if build_grid.occupied_cells.contains_key(&grid_pos) {
+        return None;
+    }
+    let entity = spawn_structure(commands, grid_to_world(grid_pos), config, difficulty);
+    build_grid.occupied_cells.insert(grid_pos, entity);
+    Some(grid_pos)
+}
+
 /// Left-click in build mode: place a structure on the grid
 pub fn place_structure(
     mut commands: Commands,
     mut build_mode: ResMut<BuildMode>,
     mut build_grid: ResMut<BuildGrid>,
     input: Res<ButtonInput<MouseButton>>,
-    window: Query<&Window, With<PrimaryWindow>>,
-    camera: Query<(&Camera, &GlobalTransform), With<GameCamera>>,
+    difficulty: Res<Difficulty>,
+    cursor_world: Res<CursorWorld>,
     mut player_query: Query<&mut Inventory, With<Player>>,
 ) {
     let Some(selected) = build_mode.selected else { return };
@@ -224,37 +236,25 @@ pub fn place_structure(
         return;
     }
 
-    let Ok(window) = window.single() else { return };
-    let Ok((cam, cam_transform)) = camera.single() else { return };
     let Ok(mut inventory) = player_query.single_mut() else { return };
 
-    let Some(cursor_world) = window.cursor_position()
-        .and_then(|cursor| cam.viewport_to_world(cam_transform, cursor).ok())
-        .map(|ray| ray.origin.truncate())
-    else { return };
+    let Some(cursor_world) = cursor_world.0 else { return };
 
-    let grid_pos = world_to_grid(cursor_world);
-
-    // Can't place on an occupied cell
-    if build_grid.occupied_cells.contains_key(&grid_pos) {
-        info!("Cell {:?} is occupied!", grid_pos);
-        return;
-    }
-
-    // Consume from inventory
-    if !remove_placeable(&mut inventory, selected) {
+    if !has_placeable(&inventory, selected) {
         info!("No more of that placeable!");
         build_mode.selected = None;
         return;
     }
 
-    // Spawn the structure
+    // Can't place on an occupied cell
     let config = PlaceableConfig::from_type(&selected);
-    let world_pos = grid_to_world(grid_pos);
-    let entity = spawn_structure(&mut commands, world_pos, &config);
+    let Some(grid_pos) = place_in_grid(&mut commands, &mut build_grid, cursor_world, &config, &difficulty) else {
+        info!("Cell {:?} is occupied!", world_to_grid(cursor_world));
+        return;
+    };
 
-    // Register in grid
-    build_grid.occupied_cells.insert(grid_pos, entity);
+    // Consume from inventory
+    remove_placeable(&mut inventory, selected);
     info!("Placed {:?} at {:?}", config.name, grid_pos);
 
     // Exit build mode if player has no more of this placeable
@@ -1,26 +1,188 @@
 use bevy::prelude::*;
 
+use crate::combat::{DamageEvent, Health};
+use crate::difficulty::Difficulty;
+use crate::spawning::{DroneConfig, WaveDirector, ring_position, spawn_drone};
 use crate::state::GameState;
 
+/// Charging waits this long after the beacon last took damage
+const UNDER_FIRE_GRACE_SECS: f32 = 3.0;
+/// Charging stops while the beacon is below this fraction of its max health
+const LOW_HEALTH_FRACTION: f32 = 0.25;
+/// Seconds between defense spawns at 0% and 100% charge
+const DEFENSE_INTERVAL_START_SECS: f32 = 12.0;
+const DEFENSE_INTERVAL_END_SECS: f32 = 4.0;
+
+const CHARGE_BAR_WIDTH: f32 = 32.0;
+const CHARGE_BAR_OFFSET: f32 = 32.0;
+const CHARGE_COLOR: Color = Color::srgb(0.0, 0.8, 1.0);
+const PAUSED_COLOR: Color = Color::srgb(1.0, 0.5, 0.0);
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BeaconStatus {
+    Charging,
+    /// Took damage recently
+    UnderFire,
+    /// Below LOW_HEALTH_FRACTION health
+    Damaged,
+    Charged,
+}
+
 #[derive(Component)]
-pub struct ExtractionBeacon(pub Timer);
+pub struct ExtractionBeacon {
+    pub charge: Timer,
+    /// Restarted on every hit, charging is paused until it finishes
+    pub under_fire: Timer,
+    /// Drones are sent at the beacon every time this finishes, faster as it charges
+    pub defense_timer: Timer,
+    pub status: BeaconStatus,
+}
 
 impl ExtractionBeacon {
     pub fn new(secs: f32) -> Self {
-        Self(Timer::from_seconds(secs, TimerMode::Once))
+        let mut under_fire = Timer::from_seconds(UNDER_FIRE_GRACE_SECS, TimerMode::Once);
+        under_fire.finish();
+
+        Self {
+            charge: Timer::from_seconds(secs, TimerMode::Once),
+            under_fire,
+            defense_timer: Timer::from_seconds(DEFENSE_INTERVAL_START_SECS, TimerMode::Repeating),
+            status: BeaconStatus::Charging,
+        }
+    }
+
+    /// 0.0 - 1.0
+    pub fn progress(&self) -> f32 {
+        self.charge.fraction()
     }
 }
 
+/// Marks the fill sprite of the charge bar drawn over the beacon
+#[derive(Component)]
+pub struct BeaconChargeFill;
+
 //when the beacon is 'full charge' the player wins
+//charging pauses while it is under fire or badly damaged
 pub fn tick_beacon_charge(
-    mut beacon_query: Query<&mut ExtractionBeacon>, //can only place one?
+    mut beacon_query: Query<(&mut ExtractionBeacon, Option<&Health>)>, //can only place one?
     time: Res<Time>,
     mut next_state: ResMut<NextState<GameState>>,
 ){
-    if let Ok(mut beacon) = beacon_query.single_mut(){
-        beacon.0.tick(time.delta());
-        if beacon.0.is_finished(){
+    if let Ok((mut beacon, health)) = beacon_query.single_mut(){
+        beacon.under_fire.tick(time.delta());
+
+        let low_health = health
+            .is_some_and(|h| (h.current as f32) < h.max as f32 * LOW_HEALTH_FRACTION);
+
+        beacon.status = if !beacon.under_fire.is_finished() {
+            BeaconStatus::UnderFire
+        } else if low_health {
+            BeaconStatus::Damaged
+        } else {
+            BeaconStatus::Charging
+        };
+
+        if beacon.status == BeaconStatus::Charging {
+            beacon.charge.tick(time.delta());
+        }
+
+        if beacon.charge.is_finished(){
+            beacon.status = BeaconStatus::Charged;
             next_state.set(GameState::Victory);
         }
-    } 
-}
\ No newline at end of file
+    }
+}
+
+/// Any damage to the beacon restarts its under-fire timer
+pub fn detect_beacon_damage(
+    mut damage_messages: MessageReader<DamageEvent>,
+    mut beacon_query: Query<&mut ExtractionBeacon>,
+) {
+    for event in damage_messages.read() {
+        if let Ok(mut beacon) = beacon_query.get_mut(event.target) {
+            beacon.under_fire.reset();
+        }
+    }
+}
+
+/// While the beacon charges, drones are sent straight at it.
+/// Waves get bigger and come more often the closer it is to full charge
+pub fn beacon_defense_spawns(
+    mut commands: Commands,
+    time: Res<Time>,
+    director: Res<WaveDirector>,
+    difficulty: Res<Difficulty>,
+    mut beacon_query: Query<(Entity, &Transform, &mut ExtractionBeacon)>,
+) {
+    let Ok((beacon_entity, transform, mut beacon)) = beacon_query.single_mut() else { return };
+
+    let progress = beacon.progress();
+    let interval = DEFENSE_INTERVAL_START_SECS
+        + (DEFENSE_INTERVAL_END_SECS - DEFENSE_INTERVAL_START_SECS) * progress;
+    beacon.defense_timer.set_duration(std::time::Duration::from_secs_f32(interval));
+    beacon.defense_timer.tick(time.delta());
+
+    if !beacon.defense_timer.just_finished() {
+        return;
+    }
+
+    let count = 1 + (progress * 4.0) as usize;
+    let pos = transform.translation.truncate();
+    info!("Beacon defense: {} drones inbound ({:.0}% charged)", count, progress * 100.0);
+
+    for i in 0..count {
+        // Mostly chasers, every third one is a shooter
+        let config = if i % 3 == 2 { DroneConfig::shooter() } else { DroneConfig::chaser() };
+        let spawn_pos = ring_position(pos, i, count, director.spawn_distance);
+        spawn_drone(&mut commands, &config, &difficulty, spawn_pos, beacon_entity);
+    }
+}
+
+/// Draws a charge bar above the beacon (above its health bar)
+pub fn attach_beacon_charge_bar(
+    mut commands: Commands,
+    query: Query<Entity, Added<ExtractionBeacon>>,
+) {
+    for entity in &query {
+        commands.entity(entity).with_children(|parent| {
+            // Background (gray)
+            parent.spawn((
+                Sprite {
+                    color: Color::srgb(0.3, 0.3, 0.3),
+                    custom_size: Some(Vec2::new(CHARGE_BAR_WIDTH, 4.0)),
+                    ..default()
+                },
+                Transform::from_xyz(0.0, CHARGE_BAR_OFFSET, 0.0),
+            ));
+
+            // Foreground (charge fill, starts empty)
+            parent.spawn((
+                Sprite {
+                    color: CHARGE_COLOR,
+                    custom_size: Some(Vec2::new(0.0, 4.0)),
+                    ..default()
+                },
+                Transform::from_xyz(0.0, CHARGE_BAR_OFFSET, 0.1),
+                BeaconChargeFill,
+            ));
+        });
+    }
+}
+
+pub fn update_beacon_charge_bar(
+    beacon_query: Query<&ExtractionBeacon>,
+    mut fill_query: Query<(&ChildOf, &mut Sprite, &mut Transform), With<BeaconChargeFill>>,
+) {
+    for (child, mut sprite, mut transform) in &mut fill_query {
+        if let Ok(beacon) = beacon_query.get(child.parent()) {
+            let width = CHARGE_BAR_WIDTH * beacon.progress();
+            sprite.custom_size = Some(Vec2::new(width, 4.0));
+            // Keep the fill left-aligned with the background
+            transform.translation.x = (width - CHARGE_BAR_WIDTH) / 2.0;
+            sprite.color = match beacon.status {
+                BeaconStatus::UnderFire | BeaconStatus::Damaged => PAUSED_COLOR,
+                _ => CHARGE_COLOR,
+            };
+        }
+    }
+}
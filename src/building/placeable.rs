@@ -1,15 +1,21 @@
 use bevy::prelude::*;
 
-use crate::{ascii_sprite::AsciiSprite, combat::{Health, HealthBar}, physics::CircleHitBox, spawning::BehaviorConfig};
+use crate::{ascii_sprite::AsciiSprite, combat::{Health, HealthBar}, difficulty::Difficulty, physics::Collider};
 use super::extraction_beacon::ExtractionBeacon;
+use super::turret::Turret;
 
-#[derive(Clone, Copy, Hash, PartialEq, Eq)]
+#[derive(Clone, Copy, Hash, PartialEq, Eq, Debug)]
 pub enum PlaceableType{
     ExtractionBeacon,
     Turret,
     Wall
 }
 
+impl PlaceableType {
+    /// Placeables that have a config
+    pub const BUILDABLE: [PlaceableType; 3] = [PlaceableType::ExtractionBeacon, PlaceableType::Turret, PlaceableType::Wall];
+}
+
 #[derive(Component)]
 pub struct Structure;
 
@@ -17,6 +23,7 @@ pub fn spawn_structure(
     commands: &mut Commands,
     pos: Vec2,
     config: &PlaceableConfig,
+    difficulty: &Difficulty,
 ) -> Entity {
     let mut entity = commands.spawn((
         Transform::from_translation(pos.extend(0.0)),
@@ -29,8 +36,8 @@ pub fn spawn_structure(
         Structure,
     ));
 
-    if let Some(hitbox_radius) = config.hitbox_radius {
-        entity.insert(CircleHitBox { radius: hitbox_radius });
+    if let Some(hitbox) = config.hitbox {
+        entity.insert(hitbox);
     }
 
     if let Some(health) = config.health {
@@ -40,23 +47,12 @@ pub fn spawn_structure(
         }
     }
 
-    // Attach behaviors (for turrets later - note: turrets need different targeting than drones)
-    for behavior in &config.behaviors {
-        match behavior {
-            BehaviorConfig::ShootAtTarget { cooldown_secs, projectile } => {
-                // TODO: Turrets need to find enemies dynamically, not a fixed target
-                // For now, skip - will need a TurretTargeting component instead
-                warn!("ShootAtTarget on structures not yet implemented");
-            }
-            _ => {
-                // Other behaviors (CollideTarget, ExplodeOnContact, MaintainRange)
-                // don't make sense for stationary structures
-            }
-        }
+    if let Some(charge_secs) = config.charge_time_secs {
+        entity.insert(ExtractionBeacon::new(charge_secs * difficulty.beacon_charge_time));
     }
 
-    if let Some(charge_secs) = config.charge_time_secs {
-        entity.insert(ExtractionBeacon::new(charge_secs));
+    if let Some(range) = config.turret_range {
+        entity.insert(Turret::new(range));
     }
 
     entity.id()
@@ -72,17 +68,18 @@ pub struct PlaceableConfig {
     pub font_size: f32,
     pub health: Option<u32>,           // None = invincibl
     pub has_health_bar: bool,
-    pub hitbox_radius: Option<f32>,    // None = no collision
-    pub behaviors: Vec<BehaviorConfig>, // empty for passive structures
+    pub hitbox: Option<Collider>,      // None = no collision
     // beacon-specific
     pub charge_time_secs: Option<f32>, // only Some for beacon (for now heheheheh)
+    // turret-specific
+    pub turret_range: Option<f32>,     // fires at drones this close
 }
 
 impl PlaceableConfig {
     pub fn from_type(placeable_type: &PlaceableType) -> Self {
         match placeable_type {
             PlaceableType::ExtractionBeacon => Self::extraction_beacon(),
-            PlaceableType::Turret => todo!("Turret config"),
+            PlaceableType::Turret => Self::turret(),
             PlaceableType::Wall => Self::wall(),
         }
     }
@@ -95,9 +92,9 @@ impl PlaceableConfig {
             color: Color::WHITE,
             font_size: 24.0,
             health: Some(100),
-            hitbox_radius: Some(12.0),
-            behaviors: vec![], 
+            hitbox: Some(Collider::circle(12.0)),
             charge_time_secs: Some(60.0),
+            turret_range: None,
             has_health_bar: true,
         }
     }
@@ -109,11 +106,24 @@ impl PlaceableConfig {
             color: Color::linear_rgb(1.0, 20.0, 100.0),
             font_size: 48.0,
             health: Some(50),
-            hitbox_radius: Some(16.0),
-            behaviors: vec![],
+            hitbox: Some(Collider::Aabb { half_size: Vec2::splat(16.0) }),
+            charge_time_secs: None,
+            turret_range: None,
+            has_health_bar: true,
+        }
+    }
+    pub fn turret() -> Self {
+        Self {
+            kind: PlaceableType::Turret,
+            name: "Turret",
+            glyph: "T",
+            color: Color::srgb(0.9, 0.9, 0.3),
+            font_size: 32.0,
+            health: Some(30),
+            hitbox: Some(Collider::circle(12.0)),
             charge_time_secs: None,
+            turret_range: Some(350.0),
             has_health_bar: true,
         }
     }
-    //pub fn turret() -> Self { ... }  // later
 }
\ No newline at end of file
@@ -0,0 +1,49 @@
+//Turrets shoot the nearest drone in range with the [turret] gun from the weapons file.
+//Their shots hit drones like the player's, but they stay out of the run summary
+use bevy::prelude::*;
+use crate::combat::{Dead, Shooter, ShotOwner, WeaponDefinitions, WeaponId, fire_pattern};
+use crate::enemy::Enemy;
+
+/// Stationary gun, added by spawn_structure for placeables with a turret_range
+#[derive(Component)]
+pub struct Turret {
+    pub range: f32,
+    /// Counts up to the turret gun's cooldown
+    since_last_shot: f32,
+}
+
+impl Turret {
+    pub fn new(range: f32) -> Self {
+        Self { range, since_last_shot: 0.0 }
+    }
+}
+
+pub fn turret_shoot(
+    mut commands: Commands,
+    time: Res<Time>,
+    weapons: Res<WeaponDefinitions>,
+    mut turrets: Query<(Entity, &Transform, &mut Turret)>,
+    enemies: Query<(&Transform, Has<Dead>), With<Enemy>>,
+){
+    // no turret gun in the weapons file, turrets just sit there
+    let Some(gun) = weapons.get(WeaponId::TURRET) else { return };
+
+    for (entity, transform, mut turret) in &mut turrets {
+        turret.since_last_shot += time.delta_secs();
+        if turret.since_last_shot < gun.fire_cooldown_secs {
+            continue;
+        }
+
+        let position = transform.translation.truncate();
+        let nearest = enemies.iter()
+            .filter(|(_, dead)| !dead)
+            .map(|(enemy_transform, _)| enemy_transform.translation.truncate())
+            .filter(|enemy_position| enemy_position.distance(position) <= turret.range)
+            .min_by(|a, b| a.distance_squared(position).total_cmp(&b.distance_squared(position)));
+        let Some(target) = nearest else { continue };
+
+        turret.since_last_shot = 0.0;
+        let shooter = Shooter { entity, position, owner: ShotOwner::Turret };
+        fire_pattern(&mut commands, &shooter, (target - position).normalize_or_zero(), gun.fire_pattern, &gun.projectile, 1.0);
+    }
+}
@@ -1,4 +1,6 @@
 use bevy::prelude::*;
+use bevy::input::InputSystems;
+use bevy::window::PrimaryWindow;
 use crate::player::Player;
 use crate::state::GameState;
 use crate::helpers::lerp;
@@ -7,29 +9,115 @@ const LERP_VAL: f32 = 0.1;
 #[derive(Component)]
 pub struct GameCamera;
 
+/// Shake on top of following the player (see hit_feedback)
+#[derive(Component, Default)]
+pub struct CameraShake {
+    timer: Option<Timer>,
+    strength: f32,
+    /// What shake_camera added this frame, taken back off before it's moved again
+    offset: Vec2,
+}
+
+impl CameraShake {
+    /// A new shake replaces the one going on unless that one is still stronger
+    pub fn start(&mut self, strength: f32, secs: f32) {
+        let current = match &self.timer {
+            Some(timer) => self.strength * (1.0 - timer.fraction()),
+            None => 0.0,
+        };
+        if strength >= current {
+            self.strength = strength;
+            self.timer = Some(Timer::from_seconds(secs, TimerMode::Once));
+        }
+    }
+}
+
+/// Where the mouse is in world space this frame (None if it's outside the window).
+/// Gameplay reads this instead of the window so replays can feed in a recorded position
+#[derive(Resource, Default)]
+pub struct CursorWorld(pub Option<Vec2>);
+
+/*This was hard for me to wrap my little pea brain around so i will explain
+* window_cursor position gets where on the screen we clicked, useless on its own.
+* viewport_to_world translates where we clicked on monitor to that in the world.
+* It does that by taking the world point that the camera is looking at, and doing
+* math to what the screen click would equal to to world location. (returns Ray3d)
+* We then truncate that ray to get the vec 2.
+*/
+pub fn update_cursor_world(
+    window: Query<&Window, With<PrimaryWindow>>,
+    camera: Query<(&Camera, &GlobalTransform), With<GameCamera>>,
+    mut cursor_world: ResMut<CursorWorld>,
+) {
+    let (Ok(window), Ok((cam, cam_transform))) = (window.single(), camera.single()) else {
+        cursor_world.0 = None;
+        return;
+    };
+
+    cursor_world.0 = window.cursor_position()
+        .and_then(|cursor| cam.viewport_to_world(cam_transform, cursor).ok())
+        .map(|ray| ray.origin.truncate());
+}
+
 fn spawn_camera(mut commands: Commands){
     commands.spawn((
         Camera2d,
         GameCamera,
+        CameraShake::default(),
     ));
 }
 
 fn follow_player(
     player_query: Query<&Transform, With<Player>>,
-    mut camera_query: Query<&mut Transform, (With<GameCamera>, Without<Player>)>,
+    mut camera_query: Query<(&mut Transform, &mut CameraShake), Without<Player>>, // only the GameCamera shakes
 ){
     let Ok(player_transform) = player_query.single() else { return; };
-    let Ok(mut camera_transform) = camera_query.single_mut() else { return; };
+    let Ok((mut camera_transform, mut shake)) = camera_query.single_mut() else { return; };
+
+    // follow from where the camera would be without the shake
+    camera_transform.translation.x -= shake.offset.x;
+    camera_transform.translation.y -= shake.offset.y;
+    shake.offset = Vec2::ZERO;
 
     camera_transform.translation.x = lerp(camera_transform.translation.x, player_transform.translation.x, LERP_VAL);
     camera_transform.translation.y = lerp(camera_transform.translation.y, player_transform.translation.y, LERP_VAL);
 }
 
+// wobbles on sine waves instead of random numbers so it doesn't touch the run's GameRng
+fn shake_camera(
+    time: Res<Time>,
+    camera: Single<(&mut Transform, &mut CameraShake), With<GameCamera>>,
+){
+    let (mut transform, mut shake) = camera.into_inner();
+    // already taken off by follow_player, unless there was no player to follow
+    transform.translation.x -= shake.offset.x;
+    transform.translation.y -= shake.offset.y;
+
+    let offset = match shake.timer.as_mut() {
+        Some(timer) => {
+            timer.tick(time.delta());
+            let t = timer.elapsed_secs();
+            let fade = 1.0 - timer.fraction();
+            Vec2::new((t * 90.0).sin(), (t * 73.0).cos()) * shake.strength * fade
+        }
+        None => Vec2::ZERO,
+    };
+    if shake.timer.as_ref().is_some_and(Timer::is_finished) {
+        shake.timer = None;
+    }
+
+    shake.offset = offset;
+    transform.translation.x += offset.x;
+    transform.translation.y += offset.y;
+}
+
 pub struct CameraPlugin;
 
 impl Plugin for CameraPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, spawn_camera)
-           .add_systems(Update, follow_player.run_if(in_state(GameState::Playing)));
+        app.init_resource::<CursorWorld>()
+           .add_systems(Startup, spawn_camera)
+           .add_systems(PreUpdate, update_cursor_world.after(InputSystems))
+           .add_systems(Update, (follow_player, shake_camera).chain().run_if(in_state(GameState::Playing)));
     }
 }
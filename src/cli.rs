@@ -0,0 +1,94 @@
+use bevy::prelude::*;
+
+use crate::combat::WeaponDefinitions;
+use crate::inventory::InventoryItem;
+
+/// Options passed on the command line, e.g. `cargo run -- --seed 1234 --record bug.rec`
+#[derive(Resource, Default, Clone, Debug)]
+pub struct CliArgs {
+    /// Every run uses this seed instead of a random one
+    pub seed: Option<u64>,
+    /// Write each run's input to this file (see replay.rs)
+    pub record: Option<String>,
+    /// Play back a recording made with --record
+    pub replay: Option<String>,
+    /// Gameplay simulation steps per second (FixedUpdate)
+    pub tick_rate: Option<f64>,
+
+    // testing / cheats
+    /// Runs start at this wave (1-based)
+    pub start_wave: Option<usize>,
+    /// The player takes no damage
+    pub god_mode: bool,
+    /// Extra starting items, from `--give scrap:10` (can be repeated)
+    pub give: Vec<(InventoryItem, u32)>,
+    /// Go to the main menu as soon as assets are loaded
+    pub skip_loading: bool,
+}
+
+/// `name` or `name:count`, e.g. `scrap:10`, `shotgun`, `wall:4`
+fn parse_give(value: &str, weapons: &WeaponDefinitions) -> Option<(InventoryItem, u32)> {
+    let (name, count) = match value.split_once(':') {
+        Some((name, count)) => (name, count.parse().ok()?),
+        None => (value, 1),
+    };
+    Some((InventoryItem::from_name(name, weapons)?, count))
+}
+
+impl CliArgs {
+    /// --wave, --god or --give, which change the run in ways recordings don't capture
+    pub fn has_cheats(&self) -> bool {
+        self.start_wave.is_some() || self.god_mode || !self.give.is_empty()
+    }
+
+    /// Needs the weapon definitions so `--give` can find weapons by name
+    pub fn parse(weapons: &WeaponDefinitions) -> Self {
+        Self::parse_from(std::env::args().skip(1), weapons)
+    }
+
+    /// Accepts `--flag value` and `--flag=value`. Unknown or malformed flags are logged and skipped
+    pub fn parse_from(args: impl IntoIterator<Item = String>, weapons: &WeaponDefinitions) -> Self {
+        let mut cli = CliArgs::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            let (flag, inline_value) = match arg.split_once('=') {
+                Some((flag, value)) => (flag.to_string(), Some(value.to_string())),
+                None => (arg.clone(), None),
+            };
+            let mut value = || inline_value.clone().or_else(|| args.next());
+
+            match flag.as_str() {
+                "--seed" => match value().and_then(|v| v.parse().ok()) {
+                    Some(seed) => cli.seed = Some(seed),
+                    None => warn!("--seed expects a number"),
+                },
+                "--tick-rate" => match value().and_then(|v| v.parse().ok()).filter(|hz: &f64| *hz > 0.0) {
+                    Some(hz) => cli.tick_rate = Some(hz),
+                    None => warn!("--tick-rate expects a positive number"),
+                },
+                "--record" => match value() {
+                    Some(path) => cli.record = Some(path),
+                    None => warn!("--record expects a file path"),
+                },
+                "--replay" => match value() {
+                    Some(path) => cli.replay = Some(path),
+                    None => warn!("--replay expects a file path"),
+                },
+                "--wave" => match value().and_then(|v| v.parse().ok()).filter(|wave: &usize| *wave > 0) {
+                    Some(wave) => cli.start_wave = Some(wave),
+                    None => warn!("--wave expects a wave number (1 = first wave)"),
+                },
+                "--give" => match value().and_then(|v| parse_give(&v, weapons)) {
+                    Some(item) => cli.give.push(item),
+                    None => warn!("--give expects an item name, optionally with a count (e.g. scrap:10)"),
+                },
+                "--god" => cli.god_mode = true,
+                "--skip-loading" => cli.skip_loading = true,
+                _ => warn!("Unknown argument: {}", arg),
+            }
+        }
+
+        cli
+    }
+}
@@ -1,14 +1,24 @@
 pub mod projectile;
 pub mod weapon;
+pub mod fire_pattern;
 pub mod collision;
 pub mod damage;
 pub mod health;
+pub mod status;
+pub mod hit_feedback;
+pub mod healing;
+pub mod grenade;
 
 pub use collision::*;
 pub use projectile::*;
 pub use weapon::*;
+pub use fire_pattern::*;
 pub use damage::*;
 pub use health::*;
+pub use status::*;
+pub use hit_feedback::*;
+pub use healing::*;
+pub use grenade::*;
 
 use bevy::prelude::*;
 
@@ -18,8 +28,13 @@ impl Plugin for CombatPlugin {
     fn build(&self, app: &mut App) {
         app
             .add_plugins(projectile::ProjectilePlugin)
+            .add_plugins(fire_pattern::FirePatternPlugin)
             .add_plugins(collision::CollisionPlugin)
             .add_plugins(damage::DamagePlugin)
-            .add_plugins(health::HealthPlugin);
+            .add_plugins(health::HealthPlugin)
+            .add_plugins(status::StatusPlugin)
+            .add_plugins(hit_feedback::HitFeedbackPlugin)
+            .add_plugins(healing::HealingPlugin)
+            .add_plugins(grenade::GrenadePlugin);
     }
 } 
\ No newline at end of file
@@ -1,42 +1,143 @@
 use bevy::prelude::*;
+use bevy::ecs::system::SystemParam;
 use crate::building::Structure;
 use crate::combat::Dead;
 use crate::enemy::Enemy;
 use crate::npc_behaviors::ExplodeOnContact;
-use crate::physics::{CircleHitBox, circles_overlap};
+use crate::physics::{Collider, SimulationSet};
 use crate::player::Player;
 use crate::state::GameState;
-use super::projectile::{Projectile, PlayerOwned, EnemyOwned};
+use super::projectile::{Projectile, PlayerOwned, EnemyOwned, PreviousPosition, SourceWeapon, Pierce};
 use super::damage::{ProjectileDamage, DamageEvent};
+use super::hit_feedback::KnockbackEvent;
+use super::fire_pattern::{Beam, ShotOwner};
+use super::status::{Elemental, apply_element};
 use crate::audio::{AudioSettings, play_sfx};
+use crate::run_stats::RunStats;
+
+/// A player bullet with what it needs to hit something: where it moved this step, its hitbox,
+/// and what it does to whatever it hits
+type PlayerProjectileHit = (
+    Entity,
+    &'static Transform,
+    &'static PreviousPosition,
+    &'static Collider,
+    &'static ProjectileDamage,
+    Option<&'static SourceWeapon>,
+    &'static mut Pierce,
+    Option<&'static Elemental>,
+);
+
+type EnemyProjectileHit = (Entity, &'static Transform, &'static PreviousPosition, &'static Collider, &'static ProjectileDamage);
+type EnemyProjectileFilter = (With<Projectile>, With<EnemyOwned>);
+
+/// Drones that can still bump into things
+type ContactEnemy = (Entity, &'static Transform, &'static Collider, Option<&'static ExplodeOnContact>);
+type LiveEnemy = (With<Enemy>, Without<Dead>);
 
 // Player bullets hit enemies
 fn player_projectile_hits_enemy(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     mut damage_messages: MessageWriter<DamageEvent>,
-    projectiles: Query<(Entity, &Transform, &CircleHitBox, &ProjectileDamage), (With<Projectile>, With<PlayerOwned>)>,
-    enemies: Query<(Entity, &Transform, &CircleHitBox), With<Enemy>>,
+    mut projectiles: Query<PlayerProjectileHit, With<PlayerOwned>>,
+    enemies: Query<(Entity, &Transform, &Collider), With<Enemy>>,
     sound_setting: Res<AudioSettings>,
+    mut stats: ResMut<RunStats>,
 ) {
-    for (proj_entity, proj_transform, proj_hitbox, damage) in &projectiles {
-        for (enemy_entity, enemy_transform, enemy_hitbox) in &enemies {
-            if circles_overlap(
-                proj_transform.translation.truncate(),
-                proj_hitbox.radius,
-                enemy_transform.translation.truncate(),
-                enemy_hitbox.radius,
-            ) {
-                info!("SOund settings: {},{},{}", sound_setting.master,sound_setting.music,sound_setting.sfx);
-                play_sfx(&mut commands, &asset_server, "player_shoot", "mp3", &sound_setting);
+    for (proj_entity, proj_transform, previous, proj_hitbox, damage, source, mut pierce, elemental) in &mut projectiles {
+        // Projectile hits one enemy per step, the first one along its path it hasn't hit yet
+        let hit = enemies.iter()
+            .filter(|(enemy_entity, _, _)| !pierce.already_hit.contains(enemy_entity))
+            .filter_map(|(enemy_entity, enemy_transform, enemy_hitbox)| {
+                proj_hitbox.swept_overlap(
+                    previous.0,
+                    proj_transform.translation.truncate(),
+                    enemy_hitbox,
+                    enemy_transform.translation.truncate(),
+                ).map(|fraction| (enemy_entity, fraction))
+            })
+            .min_by(|(_, a), (_, b)| a.total_cmp(b));
+
+        if let Some((enemy_entity, _)) = hit {
+            info!("SOund settings: {},{},{}", sound_setting.master,sound_setting.music,sound_setting.sfx);
+            play_sfx(&mut commands, &asset_server, "player_shoot", "mp3", &sound_setting);
+            if pierce.remaining == 0 {
                 commands.entity(proj_entity).despawn();
-                info!("Player projectile hit enemy!");
-                damage_messages.write(DamageEvent {
-                    target: enemy_entity,
-                    amount: damage.0,
-                });
-                break; // Projectile can only hit one enemy
+            } else {
+                pierce.remaining -= 1;
+                pierce.already_hit.push(enemy_entity);
+            }
+            info!("Player projectile hit enemy!");
+            if let Some(source) = source {
+                stats.record_hit(source.0);
             }
+            if let Some(elemental) = elemental {
+                apply_element(&mut commands, enemy_entity, elemental.0);
+            }
+            damage_messages.write(DamageEvent {
+                target: enemy_entity,
+                amount: damage.0,
+            });
+        }
+    }
+}
+
+/// What a beam can hit, depending on whose it is
+#[derive(SystemParam)]
+struct BeamTargets<'w, 's> {
+    enemies: Query<'w, 's, (Entity, &'static Transform, &'static Collider), With<Enemy>>,
+    player: Query<'w, 's, (Entity, &'static Transform, &'static Collider), With<Player>>,
+    structures: Query<'w, 's, (Entity, &'static Transform, &'static Collider), With<Structure>>,
+}
+
+impl BeamTargets<'_, '_> {
+    fn for_owner(&self, owner: ShotOwner) -> Vec<(Entity, &Transform, &Collider)> {
+        match owner {
+            ShotOwner::Player(_) | ShotOwner::Turret => self.enemies.iter().collect(),
+            ShotOwner::Enemy => self.player.iter().chain(self.structures.iter()).collect(),
+        }
+    }
+}
+
+// Beams hit on the step they fire: player beams hit enemies, enemy beams hit the player
+// and structures. Each hits up to its pierce + 1 targets and is drawn cut off at the last one.
+// A held player beam fires every step but only some of them carry damage
+fn beam_hits(
+    mut commands: Commands,
+    mut damage_messages: MessageWriter<DamageEvent>,
+    mut knockback_messages: MessageWriter<KnockbackEvent>,
+    mut beams: Query<(&Beam, &mut Sprite), Added<Beam>>,
+    beam_targets: BeamTargets,
+    mut stats: ResMut<RunStats>,
+) {
+    for (beam, mut sprite) in &mut beams {
+        let targets = beam_targets.for_owner(beam.owner);
+        let hits = beam.hits(targets.into_iter().map(|(entity, transform, hitbox)| {
+            (entity, transform.translation.truncate(), hitbox)
+        }));
+
+        for (target, _) in &hits {
+            if beam.damage == 0 {
+                continue;
+            }
+            if let ShotOwner::Player(weapon_id) = beam.owner {
+                stats.record_hit(weapon_id);
+            }
+            if let Some(element) = beam.element {
+                apply_element(&mut commands, *target, element);
+            }
+            damage_messages.write(DamageEvent {
+                target: *target,
+                amount: beam.damage,
+            });
+            // only the player gets pushed around, see react_to_hits
+            if matches!(beam.owner, ShotOwner::Enemy) {
+                knockback_messages.write(KnockbackEvent { target: *target, from: beam.start });
+            }
+        }
+        if let Some(length) = beam.cut_off_at(&hits) {
+            sprite.custom_size = Some(Vec2::new(length, beam.width));
         }
     }
 }
@@ -46,21 +147,22 @@ fn enemy_projectile_hits_player(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     mut damage_messages: MessageWriter<DamageEvent>,
-    projectiles: Query<(Entity, &Transform, &CircleHitBox, &ProjectileDamage), (With<Projectile>, With<EnemyOwned>)>,
-    player: Query<(Entity, &Transform, &CircleHitBox), With<Player>>,
+    mut knockback_messages: MessageWriter<KnockbackEvent>,
+    projectiles: Query<EnemyProjectileHit, EnemyProjectileFilter>,
+    player: Query<(Entity, &Transform, &Collider), With<Player>>,
     sound_setting: Res<AudioSettings>,
 ) {
     let Ok((player_entity, player_transform, player_hitbox)) = player.single() else {
         return;
     };
 
-    for (proj_entity, proj_transform, proj_hitbox, proj_damage) in &projectiles {
-        if circles_overlap(
+    for (proj_entity, proj_transform, previous, proj_hitbox, proj_damage) in &projectiles {
+        if proj_hitbox.swept_overlap(
+            previous.0,
             proj_transform.translation.truncate(),
-            proj_hitbox.radius,
+            player_hitbox,
             player_transform.translation.truncate(),
-            player_hitbox.radius,
-        ) {
+        ).is_some() {
             //player hit sfx TODO: MIGHT NEED A BETTER SYSTEM FOR THIS
             play_sfx(&mut commands, &asset_server, "character_hit", "mp3",&sound_setting);
             commands.entity(proj_entity).despawn();
@@ -69,6 +171,7 @@ fn enemy_projectile_hits_player(
                 target: player_entity,
                 amount: proj_damage.0,
             });
+            knockback_messages.write(KnockbackEvent { target: player_entity, from: previous.0 });
         }
     }
 }
@@ -78,8 +181,9 @@ fn enemy_collides_with_player(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     mut damage_messages: MessageWriter<DamageEvent>,
-    enemies: Query<(Entity, &Transform, &CircleHitBox, Option<&ExplodeOnContact>), (With<Enemy>, Without<Dead>)>,
-    player: Query<(Entity, &Transform, &CircleHitBox), With<Player>>,
+    mut knockback_messages: MessageWriter<KnockbackEvent>,
+    enemies: Query<ContactEnemy, LiveEnemy>,
+    player: Query<(Entity, &Transform, &Collider), With<Player>>,
     sound_setting: Res<AudioSettings>,
 ) {
     let Ok((player_entity, player_transform, player_hitbox)) = player.single() else {
@@ -87,11 +191,10 @@ fn enemy_collides_with_player(
     };
 
     for (enemy_entity, enemy_transform, enemy_hitbox, explode) in &enemies {
-        if circles_overlap(
+        if enemy_hitbox.overlaps(
             enemy_transform.translation.truncate(),
-            enemy_hitbox.radius,
+            player_hitbox,
             player_transform.translation.truncate(),
-            player_hitbox.radius,
         ) {
             info!("Enemy collided with player!");
             if explode.is_some(){
@@ -104,6 +207,10 @@ fn enemy_collides_with_player(
                     target: player_entity,
                     amount: 5,
                 });
+                knockback_messages.write(KnockbackEvent {
+                    target: player_entity,
+                    from: enemy_transform.translation.truncate(),
+                });
                 // Enemy self-destructs (damage itself for its full health)
                 damage_messages.write(DamageEvent {
                     target: enemy_entity,
@@ -120,24 +227,28 @@ fn enemy_collides_with_player(
 fn enemy_projectile_hits_structure(
     mut commands: Commands,
     mut damage_messages: MessageWriter<DamageEvent>,
-    projectiles: Query<(Entity, &Transform, &CircleHitBox, &ProjectileDamage), (With<Projectile>, With<EnemyOwned>)>,
-    structures: Query<(Entity, &Transform, &CircleHitBox), With<Structure>>,
+    projectiles: Query<EnemyProjectileHit, EnemyProjectileFilter>,
+    structures: Query<(Entity, &Transform, &Collider), With<Structure>>,
 ) {
-    for (proj_entity, proj_transform, proj_hitbox, proj_damage) in &projectiles {
-        for (structure_entity, structure_transform, structure_hitbox) in &structures {
-            if circles_overlap(
-                proj_transform.translation.truncate(),
-                proj_hitbox.radius,
-                structure_transform.translation.truncate(),
-                structure_hitbox.radius,
-            ) {
-                commands.entity(proj_entity).despawn();
-                damage_messages.write(DamageEvent {
-                    target: structure_entity,
-                    amount: proj_damage.0,
-                });
-                break;
-            }
+    for (proj_entity, proj_transform, previous, proj_hitbox, proj_damage) in &projectiles {
+        // first structure along the path this step
+        let hit = structures.iter()
+            .filter_map(|(structure_entity, structure_transform, structure_hitbox)| {
+                proj_hitbox.swept_overlap(
+                    previous.0,
+                    proj_transform.translation.truncate(),
+                    structure_hitbox,
+                    structure_transform.translation.truncate(),
+                ).map(|fraction| (structure_entity, fraction))
+            })
+            .min_by(|(_, a), (_, b)| a.total_cmp(b));
+
+        if let Some((structure_entity, _)) = hit {
+            commands.entity(proj_entity).despawn();
+            damage_messages.write(DamageEvent {
+                target: structure_entity,
+                amount: proj_damage.0,
+            });
         }
     }
 }
@@ -146,29 +257,26 @@ fn enemy_projectile_hits_structure(
 fn enemy_collides_with_structure(
     mut commands: Commands,
     mut damage_messages: MessageWriter<DamageEvent>,
-    enemies: Query<(Entity, &Transform, &CircleHitBox, Option<&ExplodeOnContact>), (With<Enemy>, Without<Dead>)>,
-    structures: Query<(Entity, &Transform, &CircleHitBox), With<Structure>>,
+    enemies: Query<ContactEnemy, LiveEnemy>,
+    structures: Query<(Entity, &Transform, &Collider), With<Structure>>,
 ) {
     for (enemy_entity, enemy_transform, enemy_hitbox, explode) in &enemies {
         for (structure_entity, structure_transform, structure_hitbox) in &structures {
-            if circles_overlap(
+            if explode.is_some() && enemy_hitbox.overlaps(
                 enemy_transform.translation.truncate(),
-                enemy_hitbox.radius,
+                structure_hitbox,
                 structure_transform.translation.truncate(),
-                structure_hitbox.radius,
             ) {
-                if explode.is_some() {
-                    commands.entity(enemy_entity).insert(Dead);
-                    damage_messages.write(DamageEvent {
-                        target: structure_entity,
-                        amount: 5,
-                    });
-                    damage_messages.write(DamageEvent {
-                        target: enemy_entity,
-                        amount: 9999,
-                    });
-                    break;
-                }
+                commands.entity(enemy_entity).insert(Dead);
+                damage_messages.write(DamageEvent {
+                    target: structure_entity,
+                    amount: 5,
+                });
+                damage_messages.write(DamageEvent {
+                    target: enemy_entity,
+                    amount: 9999,
+                });
+                break;
             }
         }
     }
@@ -179,10 +287,13 @@ pub struct CollisionPlugin;
 impl Plugin for CollisionPlugin {
     fn build(&self, app: &mut App) {
         app
-            .add_systems(Update, enemy_projectile_hits_player.run_if(in_state(GameState::Playing)))
-            .add_systems(Update, player_projectile_hits_enemy.run_if(in_state(GameState::Playing)))
-            .add_systems(Update, enemy_collides_with_player.run_if(in_state(GameState::Playing)))
-            .add_systems(Update, enemy_projectile_hits_structure.run_if(in_state(GameState::Playing)))
-            .add_systems(Update, enemy_collides_with_structure.run_if(in_state(GameState::Playing)));
+            .add_systems(FixedUpdate, (
+                enemy_projectile_hits_player,
+                player_projectile_hits_enemy,
+                beam_hits,
+                enemy_collides_with_player,
+                enemy_projectile_hits_structure,
+                enemy_collides_with_structure,
+            ).in_set(SimulationSet::Collision).run_if(in_state(GameState::Playing)));
     }
 }
\ No newline at end of file
@@ -2,16 +2,22 @@
 //and how much damage it took
 
 use bevy::prelude::*;
-use rand::{Rng, RngExt};
+use rand::RngExt;
 use crate::{
-    building::{BuildGrid, Structure},
+    building::{BuildGrid, ExtractionBeacon, Structure},
+    difficulty::Difficulty,
+    enemy::Enemy,
+    game_rng::GameRng,
+    physics::SimulationSet,
     player::Player,
     resources::DropTable,
-    spawning::{DroneType, spawn_resources},
-    state::GameState,
+    run_stats::RunStats,
+    spawning::{DroneType, spawn_health_pickup, spawn_resources},
+    state::{GameOverReason, GameState},
 };
 
-use super::health::Health;
+use super::health::{Dead, GodMode, Health, Invulnerable};
+use super::hit_feedback::{HitReaction, HitTaken};
 
 #[derive(Message)]
 pub struct DeathEvent{
@@ -27,15 +33,42 @@ pub struct DamageEvent {
     pub amount: u32,
 }
 
-fn apply_damage(
+pub fn apply_damage(
     mut messages: MessageReader<DamageEvent>,
     mut death_messages: MessageWriter<DeathEvent>,
-    mut health_query: Query<&mut Health>,
+    mut hit_messages: MessageWriter<HitTaken>,
+    mut health_query: Query<(&mut Health, Has<Invulnerable>), Without<GodMode>>,
+    reactors: Query<(), With<HitReaction>>,
+    enemies: Query<Has<Dead>, With<Enemy>>,
+    mut stats: ResMut<RunStats>,
 ) {
+    // HitReaction targets only take the first hit of a step, their i-frames start with it
+    let mut reacted: Vec<Entity> = Vec::new();
+
     for event in messages.read() {
-        if let Ok(mut health) = health_query.get_mut(event.target){
+        if let Ok((mut health, invulnerable)) = health_query.get_mut(event.target){
+            // already died earlier this step, its DeathEvent is out
+            if invulnerable || health.current == 0 {
+                continue;
+            }
+            let reacts = reactors.contains(event.target);
+            if reacts {
+                if reacted.contains(&event.target) {
+                    continue;
+                }
+                reacted.push(event.target);
+            }
+
             //saturating sub clamps to 0
+            let before = health.current;
             health.current = health.current.saturating_sub(event.amount);
+            if reacts {
+                hit_messages.write(HitTaken { target: event.target, amount: before - health.current });
+            }
+            // only what the drone actually lost, exploders blowing themselves up don't count
+            if let Ok(false) = enemies.get(event.target) {
+                stats.damage_dealt += before - health.current;
+            }
             info!("Damage event: {:?} took {} damage", event.target, event.amount);
             if health.current == 0{
                 death_messages.write(DeathEvent { entity: event.target });
@@ -44,45 +77,67 @@ fn apply_damage(
     }
 }
 
-fn apply_death(
+/// Resources and health pickups from drones that died this step. Runs before apply_death
+/// despawns them
+pub fn spawn_death_drops(
     mut commands: Commands,
-    player_query: Single<Entity, With<Player>>,
+    player: Single<Entity, With<Player>>,
     mut death_messages: MessageReader<DeathEvent>,
-    mut next_state: ResMut<NextState<GameState>>,
     drone_query: Query<(&DroneType, &Transform)>,
-    structure_query: Query<&Transform, With<Structure>>,
-    mut build_grid: ResMut<BuildGrid>,
     drop_table: Res<DropTable>,
-    player: Single<Entity, With<Player>>,
+    difficulty: Res<Difficulty>,
+    mut rng: ResMut<GameRng>,
 ) {
-    let mut rng = rand::rng();
+    for event in death_messages.read() {
+        let Ok((drone_type, transform)) = drone_query.get(event.entity) else { continue };
+        let pos = transform.translation.truncate();
+
+        if let Some(drop_list) = drop_table.table.get(&drone_type.drone_type) {
+            for drop in drop_list {
+                let count = rng.random_range(difficulty.scale_drop(drop.min)..=difficulty.scale_drop(drop.max));
+                if count > 0 {
+                    spawn_resources(&mut commands, &mut rng, drop.resource, pos, count, *player);
+                }
+            }
+        }
+
+        if let Some(chance) = drop_table.health_chance.get(&drone_type.drone_type)
+            && rng.random_range(0.0..1.0) < chance * difficulty.drop_amount
+        {
+            spawn_health_pickup(&mut commands, &mut rng, pos);
+        }
+    }
+}
 
+pub fn apply_death(
+    mut commands: Commands,
+    player_query: Single<Entity, With<Player>>,
+    mut death_messages: MessageReader<DeathEvent>,
+    mut next_state: ResMut<NextState<GameState>>,
+    structure_query: Query<(&Transform, Has<ExtractionBeacon>), With<Structure>>,
+    mut build_grid: ResMut<BuildGrid>,
+    mut game_over_reason: ResMut<GameOverReason>,
+) {
     for event in death_messages.read() {
         if event.entity.index() == player_query.index(){
             commands.entity(event.entity).try_despawn();
             //game over phase
+            *game_over_reason = GameOverReason::PlayerKilled;
             next_state.set(GameState::GameOver);
             continue;
         }
 
         // If it's a structure, remove from grid
-        if let Ok(transform) = structure_query.get(event.entity) {
+        if let Ok((transform, is_beacon)) = structure_query.get(event.entity) {
             let grid_pos = crate::building::world_to_grid(transform.translation.truncate());
             build_grid.occupied_cells.remove(&grid_pos);
             info!("Structure destroyed at {:?}", grid_pos);
-        }
-
-        // Check if it's a drone and spawn resources
-        if let Ok((drone_type, transform)) = drone_query.get(event.entity) {
-            let pos = transform.translation.truncate();
 
-            if let Some(drop_list) = drop_table.table.get(&drone_type.drone_type) {
-                for drop in drop_list {
-                    let count = rng.random_range(drop.min..=drop.max);
-                    if count > 0 {
-                        spawn_resources(&mut commands, drop.resource, pos, count, player.entity());
-                    }
-                }
+            // Losing the beacon loses the run
+            if is_beacon {
+                info!("Extraction beacon destroyed!");
+                *game_over_reason = GameOverReason::BeaconDestroyed;
+                next_state.set(GameState::GameOver);
             }
         }
 
@@ -97,7 +152,9 @@ impl Plugin for DamagePlugin {
         app
             .add_message::<DamageEvent>()
             .add_message::<DeathEvent>()
-            .add_systems(Update, apply_damage.run_if(in_state(GameState::Playing)))
-            .add_systems(Update, apply_death.run_if(in_state(GameState::Playing)));
+            .add_systems(FixedUpdate, (apply_damage, spawn_death_drops, apply_death)
+                .chain()
+                .in_set(SimulationSet::Damage)
+                .run_if(in_state(GameState::Playing)));
     }
 }
\ No newline at end of file
@@ -0,0 +1,300 @@
+//How a trigger pull turns into projectiles. Shared by the player's weapons and
+//enemy ShootAtTarget so both can use every pattern.
+use std::f32::consts::TAU;
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+use crate::physics::{Collider, SimulationSet, Velocity, apply_velocity};
+use crate::state::GameState;
+use super::projectile::{
+    EnemyOwned, Lifetimer, PlayerOwned, Projectile, ProjectileConfig, SourceWeapon,
+    spawn_enemy_projectile, spawn_player_projectile,
+};
+use super::status::Element;
+use super::weapon::WeaponId;
+
+/// How long a beam stays on screen after it fires
+const BEAM_FLASH_SECS: f32 = 0.08;
+
+/// Describes how a weapon fires its projectiles
+#[derive(Clone, Copy)]
+pub enum FirePattern {
+    /// Single projectile straight ahead
+    Single,
+    /// Multiple projectiles in a spread (count, total spread angle in degrees)
+    Spread { count: usize, angle_degrees: f32 },
+    /// `count` shots in a row, `interval` seconds apart, all in the direction of the first
+    Burst { count: usize, interval: f32 },
+    /// Hitscan ray, hits the first thing along it (more with pierce) every time it fires.
+    /// The player's is held down and fires every step (see player_shoot)
+    Beam { range: f32, width: f32 },
+    /// Hold to charge, release to fire. Damage and size grow from 1x to `max_multiplier`
+    /// over `max_secs`. Enemies always fire fully charged
+    Charge { max_secs: f32, max_multiplier: f32 },
+    /// `count` projectiles circling the shooter until their lifetime runs out
+    Orbit { count: usize, radius: f32, degrees_per_sec: f32 },
+}
+
+/// Which side a shot belongs to, picks the spawn function and what it can hit
+#[derive(Clone, Copy)]
+pub enum ShotOwner {
+    Player(WeaponId),
+    /// Player-built turrets hit enemies like the player, but stay out of the run stats
+    Turret,
+    Enemy,
+}
+
+/// Whoever is firing, bursts and orbits stay attached to the entity
+pub struct Shooter {
+    pub entity: Entity,
+    pub position: Vec2,
+    pub owner: ShotOwner,
+}
+
+/// One projectile, owned by the right side
+fn spawn_shot(
+    commands: &mut Commands,
+    owner: ShotOwner,
+    position: Vec2,
+    direction: Vec2,
+    config: &ProjectileConfig,
+) -> Entity {
+    match owner {
+        ShotOwner::Player(weapon_id) => spawn_player_projectile(commands, position, direction, config, Some(weapon_id)),
+        ShotOwner::Turret => spawn_player_projectile(commands, position, direction, config, None),
+        ShotOwner::Enemy => spawn_enemy_projectile(commands, position, direction, config),
+    }
+}
+
+/// Fires one trigger pull of `pattern`. `charge` (0-1) only matters for Charge
+pub fn fire_pattern(
+    commands: &mut Commands,
+    shooter: &Shooter,
+    direction: Vec2,
+    pattern: FirePattern,
+    config: &ProjectileConfig,
+    charge: f32,
+) {
+    match pattern {
+        FirePattern::Single => {
+            spawn_shot(commands, shooter.owner, shooter.position, direction, config);
+        }
+        FirePattern::Spread { count, angle_degrees } => {
+            let total_rad = angle_degrees.to_radians();
+            let step = total_rad / (count - 1).max(1) as f32;
+            let start = -total_rad / 2.0;
+
+            for i in 0..count {
+                let rotated = Vec2::from_angle(start + step * i as f32).rotate(direction);
+                spawn_shot(commands, shooter.owner, shooter.position, rotated, config);
+            }
+        }
+        FirePattern::Burst { count, interval } => {
+            spawn_shot(commands, shooter.owner, shooter.position, direction, config);
+            if count > 1 {
+                commands.entity(shooter.entity).insert(BurstFire {
+                    remaining: count - 1,
+                    interval: Timer::from_seconds(interval, TimerMode::Repeating),
+                    direction,
+                    config: config.clone(),
+                    owner: shooter.owner,
+                });
+            }
+        }
+        FirePattern::Beam { range, width } => {
+            spawn_beam(commands, shooter, direction, range, width, config);
+        }
+        FirePattern::Charge { max_multiplier, .. } => {
+            let multiplier = 1.0 + (max_multiplier - 1.0) * charge.clamp(0.0, 1.0);
+            let mut charged = config.clone();
+            charged.damage = (config.damage as f32 * multiplier).round() as u32;
+            charged.size = config.size * multiplier;
+            spawn_shot(commands, shooter.owner, shooter.position, direction, &charged);
+        }
+        FirePattern::Orbit { count, radius, degrees_per_sec } => {
+            for i in 0..count {
+                let angle = direction.to_angle() + TAU * i as f32 / count as f32;
+                let position = shooter.position + Vec2::from_angle(angle) * radius;
+                let orbiter = spawn_shot(commands, shooter.owner, position, direction, config);
+                commands.entity(orbiter).insert((
+                    // position is set by orbit_projectiles, Velocity stays for interpolation
+                    Velocity { speed: 0.0, direction },
+                    Orbiting {
+                        center: shooter.entity,
+                        radius,
+                        angle,
+                        radians_per_sec: degrees_per_sec.to_radians(),
+                    },
+                ));
+            }
+        }
+    }
+}
+
+// =============================================================================
+// BURST
+// =============================================================================
+
+/// Rest of a burst, on the shooter. Replaced if it fires another burst
+#[derive(Component)]
+pub struct BurstFire {
+    pub remaining: usize,
+    pub interval: Timer,
+    pub direction: Vec2,
+    pub config: ProjectileConfig,
+    pub owner: ShotOwner,
+}
+
+pub fn fire_bursts(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut shooters: Query<(Entity, &Transform, &mut BurstFire)>,
+) {
+    for (entity, transform, mut burst) in &mut shooters {
+        burst.interval.tick(time.delta());
+        if !burst.interval.just_finished() {
+            continue;
+        }
+
+        spawn_shot(&mut commands, burst.owner, transform.translation.truncate(), burst.direction, &burst.config);
+        burst.remaining -= 1;
+        if burst.remaining == 0 {
+            commands.entity(entity).remove::<BurstFire>();
+        }
+    }
+}
+
+// =============================================================================
+// BEAM
+// =============================================================================
+
+/// Hitscan shot. Damage is dealt by the collision systems on the step it appears,
+/// after that it's only drawn until its Lifetimer runs out
+#[derive(Component)]
+pub struct Beam {
+    pub start: Vec2,
+    pub direction: Vec2,
+    pub range: f32,
+    pub width: f32,
+    pub damage: u32,
+    /// Extra targets it passes through
+    pub pierce: u32,
+    pub owner: ShotOwner,
+    pub element: Option<Element>,
+}
+
+impl Beam {
+    /// Targets along the ray, nearest first, as (entity, distance along the beam).
+    /// Stops after the first `pierce + 1`
+    pub fn hits<'a>(&self, targets: impl Iterator<Item = (Entity, Vec2, &'a Collider)>) -> Vec<(Entity, f32)> {
+        let ray = Collider::circle(self.width / 2.0);
+        let end = self.start + self.direction * self.range;
+
+        let mut hits: Vec<(Entity, f32)> = targets
+            .filter_map(|(entity, position, collider)| {
+                ray.swept_overlap(self.start, end, collider, position)
+                    .map(|fraction| (entity, fraction * self.range))
+            })
+            .collect();
+        hits.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+        hits.truncate(self.pierce as usize + 1);
+        hits
+    }
+
+    /// How far the beam gets if its last hit used up the pierce, None if it goes full range
+    pub fn cut_off_at(&self, hits: &[(Entity, f32)]) -> Option<f32> {
+        if hits.len() > self.pierce as usize {
+            hits.last().map(|(_, distance)| *distance)
+        } else {
+            None
+        }
+    }
+}
+
+fn spawn_beam(
+    commands: &mut Commands,
+    shooter: &Shooter,
+    direction: Vec2,
+    range: f32,
+    width: f32,
+    config: &ProjectileConfig,
+) {
+    let direction = direction.normalize_or_zero();
+    let mut beam = commands.spawn((
+        Transform::from_translation(shooter.position.extend(0.0))
+            .with_rotation(Quat::from_rotation_z(direction.to_angle())),
+        Sprite {
+            color: config.color,
+            custom_size: Some(Vec2::new(range, width)),
+            ..default()
+        },
+        // drawn from the shooter outwards, so cutting it short only changes the width
+        Anchor::CENTER_LEFT,
+        Beam {
+            start: shooter.position,
+            direction,
+            range,
+            width,
+            damage: config.damage,
+            pierce: config.pierce,
+            owner: shooter.owner,
+            element: config.element,
+        },
+        Projectile,
+        Lifetimer(Timer::from_seconds(BEAM_FLASH_SECS, TimerMode::Once)),
+    ));
+
+    match shooter.owner {
+        ShotOwner::Player(weapon_id) => beam.insert((PlayerOwned, SourceWeapon(weapon_id))),
+        ShotOwner::Turret => beam.insert(PlayerOwned),
+        ShotOwner::Enemy => beam.insert(EnemyOwned),
+    };
+}
+
+// =============================================================================
+// ORBIT
+// =============================================================================
+
+/// Projectile circling `center`. Despawned if the center is gone
+#[derive(Component)]
+pub struct Orbiting {
+    pub center: Entity,
+    pub radius: f32,
+    /// Current angle around the center, radians
+    pub angle: f32,
+    pub radians_per_sec: f32,
+}
+
+fn orbit_projectiles(
+    mut commands: Commands,
+    time: Res<Time>,
+    centers: Query<&Transform, Without<Orbiting>>,
+    mut orbiters: Query<(Entity, &mut Transform, &mut Orbiting)>,
+) {
+    for (entity, mut transform, mut orbit) in &mut orbiters {
+        let Ok(center) = centers.get(orbit.center) else {
+            commands.entity(entity).despawn();
+            continue;
+        };
+
+        orbit.angle += orbit.radians_per_sec * time.delta_secs();
+        let position = center.translation.truncate() + Vec2::from_angle(orbit.angle) * orbit.radius;
+        transform.translation = position.extend(transform.translation.z);
+    }
+}
+
+pub struct FirePatternPlugin;
+
+impl Plugin for FirePatternPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(FixedUpdate, fire_bursts
+                .in_set(SimulationSet::Intent)
+                .run_if(in_state(GameState::Playing)))
+            // after store_previous_positions (which runs before apply_velocity) so the
+            // orbit move gets swept by collision like any other
+            .add_systems(FixedUpdate, orbit_projectiles
+                .after(apply_velocity)
+                .in_set(SimulationSet::Movement)
+                .run_if(in_state(GameState::Playing)));
+    }
+}
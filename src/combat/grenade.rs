@@ -0,0 +1,106 @@
+//Thrown grenades. They fly to where you aimed, sit there until the fuse runs out and then
+//damage every drone in the blast radius. Walls and the player are safe
+use bevy::prelude::*;
+use crate::ascii_sprite::AsciiSprite;
+use crate::enemy::Enemy;
+use crate::physics::{InterpolatedTransform, SimulationSet};
+use crate::state::GameState;
+use super::damage::DamageEvent;
+use super::health::Dead;
+use super::projectile::Lifetimer;
+
+const GRENADE_SPEED: f32 = 500.0;
+/// Furthest it can be thrown, aiming further away lands it at this distance
+pub const GRENADE_RANGE: f32 = 300.0;
+const GRENADE_FUSE_SECS: f32 = 1.0;
+const GRENADE_RADIUS: f32 = 90.0;
+const GRENADE_DAMAGE: u32 = 4;
+
+/// How long the blast ring stays on screen
+const BLAST_SECS: f32 = 0.2;
+
+#[derive(Component)]
+pub struct Grenade {
+    /// Where it lands
+    pub target: Vec2,
+    pub fuse: Timer,
+}
+
+/// Ring left behind by an explosion, only for show. Its Lifetimer despawns it
+#[derive(Component)]
+pub struct GrenadeBlast;
+
+/// Throws a grenade from `from` towards `aim`, landing at most GRENADE_RANGE away
+pub fn spawn_grenade(commands: &mut Commands, from: Vec2, aim: Vec2) {
+    let target = from + (aim - from).clamp_length_max(GRENADE_RANGE);
+    commands.spawn((
+        Transform::from_translation(from.extend(0.0)),
+        InterpolatedTransform::default(),
+        AsciiSprite {
+            glyph: "o".to_string(),
+            color: Color::srgb(0.4, 0.8, 0.3),
+            font_size: 20.0,
+            bg_color: None,
+        },
+        Grenade {
+            target,
+            fuse: Timer::from_seconds(GRENADE_FUSE_SECS, TimerMode::Once),
+        },
+    ));
+}
+
+fn update_grenades(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut damage_messages: MessageWriter<DamageEvent>,
+    mut grenades: Query<(Entity, &mut Transform, &mut Grenade), Without<Enemy>>,
+    enemies: Query<(Entity, &Transform, Has<Dead>), With<Enemy>>,
+) {
+    for (entity, mut transform, mut grenade) in &mut grenades {
+        let position = transform.translation.truncate();
+        let moved = position.move_towards(grenade.target, GRENADE_SPEED * time.delta_secs());
+        transform.translation.x = moved.x;
+        transform.translation.y = moved.y;
+
+        grenade.fuse.tick(time.delta());
+        if !grenade.fuse.is_finished() {
+            continue;
+        }
+
+        for (enemy, enemy_transform, dead) in &enemies {
+            if !dead && enemy_transform.translation.truncate().distance(moved) <= GRENADE_RADIUS {
+                damage_messages.write(DamageEvent { target: enemy, amount: GRENADE_DAMAGE });
+            }
+        }
+        commands.entity(entity).despawn();
+        commands.spawn((
+            Transform::from_translation(moved.extend(0.0)),
+            GrenadeBlast,
+            Lifetimer(Timer::from_seconds(BLAST_SECS, TimerMode::Once)),
+        ));
+        info!("Grenade went off at {:?}", moved);
+    }
+}
+
+fn draw_grenade_blasts(
+    mut gizmos: Gizmos,
+    blasts: Query<(&Transform, &Lifetimer), With<GrenadeBlast>>,
+) {
+    for (transform, lifetime) in &blasts {
+        let progress = lifetime.0.fraction();
+        let color = Color::srgba(1.0, 0.6, 0.2, 1.0 - progress);
+        gizmos.circle_2d(transform.translation.truncate(), GRENADE_RADIUS * progress.max(0.2), color);
+    }
+}
+
+pub struct GrenadePlugin;
+
+impl Plugin for GrenadePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(FixedUpdate, update_grenades
+                .in_set(SimulationSet::Movement)
+                .run_if(in_state(GameState::Playing)))
+            .add_systems(Update, draw_grenade_blasts.run_if(in_state(GameState::Playing)));
+    }
+}
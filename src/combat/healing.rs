@@ -0,0 +1,128 @@
+//Health going back up. Medkits, health pickups and regeneration all go through
+//HealEvent, the same way everything that hurts goes through DamageEvent
+use bevy::prelude::*;
+use crate::physics::SimulationSet;
+use crate::player::Player;
+use crate::state::GameState;
+use super::damage::apply_damage;
+use super::health::Health;
+use super::hit_feedback::HitTaken;
+
+/// Seconds without taking damage before regeneration kicks in
+const REGEN_DELAY_SECS: f32 = 6.0;
+const REGEN_INTERVAL_SECS: f32 = 2.0;
+const REGEN_AMOUNT: u32 = 1;
+
+const HEALTH_PICKUP_DISTANCE: f32 = 20.0;
+
+#[derive(Message)]
+pub struct HealEvent {
+    pub target: Entity,
+    pub amount: u32,
+}
+
+/// Heals REGEN_AMOUNT every REGEN_INTERVAL_SECS once it's been REGEN_DELAY_SECS since the
+/// last hit. Needs HitReaction to notice hits
+#[derive(Component)]
+pub struct Regeneration {
+    out_of_combat: Timer,
+    tick: Timer,
+}
+
+impl Default for Regeneration {
+    fn default() -> Self {
+        Self {
+            out_of_combat: Timer::from_seconds(REGEN_DELAY_SECS, TimerMode::Once),
+            tick: Timer::from_seconds(REGEN_INTERVAL_SECS, TimerMode::Repeating),
+        }
+    }
+}
+
+/// Dropped by drones now and then (see DropTable), heals the player on contact.
+/// Left on the ground while the player is at full health
+#[derive(Component)]
+pub struct HealthPickup {
+    pub amount: u32,
+}
+
+pub fn apply_heal(
+    mut messages: MessageReader<HealEvent>,
+    mut health_query: Query<&mut Health>,
+) {
+    for event in messages.read() {
+        if let Ok(mut health) = health_query.get_mut(event.target) {
+            // already dead, apply_death will get to it
+            if health.current == 0 {
+                continue;
+            }
+            let before = health.current;
+            health.current = (health.current + event.amount).min(health.max);
+            info!("Heal event: {:?} healed {}", event.target, health.current - before);
+        }
+    }
+}
+
+fn regenerate(
+    time: Res<Time>,
+    mut hits: MessageReader<HitTaken>,
+    mut heal_messages: MessageWriter<HealEvent>,
+    mut query: Query<(Entity, &Health, &mut Regeneration)>,
+) {
+    let hit: Vec<Entity> = hits.read().map(|hit| hit.target).collect();
+
+    for (entity, health, mut regen) in &mut query {
+        if hit.contains(&entity) {
+            regen.out_of_combat.reset();
+            regen.tick.reset();
+            continue;
+        }
+
+        regen.out_of_combat.tick(time.delta());
+        if !regen.out_of_combat.is_finished() || health.current >= health.max {
+            continue;
+        }
+
+        regen.tick.tick(time.delta());
+        if regen.tick.just_finished() {
+            heal_messages.write(HealEvent { target: entity, amount: REGEN_AMOUNT });
+        }
+    }
+}
+
+fn collect_health_pickups(
+    mut commands: Commands,
+    mut heal_messages: MessageWriter<HealEvent>,
+    player: Single<(Entity, &Transform, &Health), With<Player>>,
+    pickups: Query<(Entity, &Transform, &HealthPickup)>,
+) {
+    let (player_entity, player_transform, health) = player.into_inner();
+    if health.current >= health.max {
+        return;
+    }
+
+    for (entity, transform, pickup) in &pickups {
+        let distance = transform.translation.truncate().distance(player_transform.translation.truncate());
+        if distance <= HEALTH_PICKUP_DISTANCE {
+            heal_messages.write(HealEvent { target: player_entity, amount: pickup.amount });
+            commands.entity(entity).try_despawn();
+            // one at a time so a pile of them doesn't get wasted on a small top-up
+            break;
+        }
+    }
+}
+
+pub struct HealingPlugin;
+
+impl Plugin for HealingPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_message::<HealEvent>()
+            .add_systems(FixedUpdate, collect_health_pickups
+                .in_set(SimulationSet::Collision)
+                .run_if(in_state(GameState::Playing)))
+            .add_systems(FixedUpdate, (
+                apply_heal.before(apply_damage),
+                regenerate.after(apply_damage),
+            ).in_set(SimulationSet::Damage).run_if(in_state(GameState::Playing)));
+    }
+}
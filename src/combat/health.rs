@@ -1,6 +1,9 @@
 //todo health! this will end up being like health bars that appear above the
 //entities and what not.
 use bevy::prelude::*;
+use crate::physics::SimulationSet;
+use crate::state::GameState;
+use super::damage::apply_damage;
 
 #[derive(Component)]
 pub struct Dead;
@@ -19,6 +22,26 @@ pub struct HealthBarFill {
     pub max_width: f32,
 }
 
+/// Ignores all damage (--god)
+#[derive(Component)]
+pub struct GodMode;
+
+/// Ignores all damage until the timer runs out (dash i-frames)
+#[derive(Component)]
+pub struct Invulnerable(pub Timer);
+
+/// Gives `target` i-frames for `secs`, keeping whichever lasts longer if it already has some
+pub fn make_invulnerable(commands: &mut Commands, target: Entity, secs: f32) {
+    commands.entity(target).entry::<Invulnerable>()
+        .and_modify(move |mut invulnerable| {
+            let remaining = invulnerable.0.remaining_secs();
+            if secs > remaining {
+                invulnerable.0 = Timer::from_seconds(secs, TimerMode::Once);
+            }
+        })
+        .or_insert(Invulnerable(Timer::from_seconds(secs, TimerMode::Once)));
+}
+
 #[derive(Component)]
 pub struct Health {
     pub current: u32,
@@ -71,6 +94,19 @@ fn update_health_bars(
     }
 }
 
+fn tick_invulnerability(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Invulnerable)>,
+) {
+    for (entity, mut invulnerable) in &mut query {
+        invulnerable.0.tick(time.delta());
+        if invulnerable.0.is_finished() {
+            commands.entity(entity).remove::<Invulnerable>();
+        }
+    }
+}
+
 impl Health {
     pub fn new(max: u32) -> Self {
         Self { current: max, max }
@@ -81,6 +117,10 @@ pub struct HealthPlugin;
 
 impl Plugin for HealthPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, (attach_health_bars, update_health_bars));
+        app.add_systems(Update, (attach_health_bars, update_health_bars))
+            .add_systems(FixedUpdate, tick_invulnerability
+                .before(apply_damage)
+                .in_set(SimulationSet::Damage)
+                .run_if(in_state(GameState::Playing)));
     }
 }
\ No newline at end of file
@@ -0,0 +1,161 @@
+//What happens to the player when something gets through: a moment of i-frames, the @
+//flashing, the camera shaking and a shove away from whatever hit it
+use bevy::prelude::*;
+use crate::ascii_sprite::AsciiSprite;
+use crate::camera::{CameraShake, GameCamera};
+use crate::physics::SimulationSet;
+use crate::state::GameState;
+use super::damage::{apply_damage, apply_death};
+use super::health::make_invulnerable;
+
+/// Tuning for hit feedback. Screen shake can be turned off in the pause menu settings
+#[derive(Resource)]
+pub struct HitFeedbackSettings {
+    /// Seconds of invulnerability after taking damage
+    pub iframe_secs: f32,
+    pub flash_color: Color,
+    /// Seconds between flash on / off while invulnerable
+    pub flash_interval_secs: f32,
+    pub screen_shake: bool,
+    /// Furthest the camera gets pushed, in pixels
+    pub shake_strength: f32,
+    pub shake_secs: f32,
+    pub knockback_speed: f32,
+    pub knockback_secs: f32,
+}
+
+impl Default for HitFeedbackSettings {
+    fn default() -> Self {
+        Self {
+            iframe_secs: 0.6,
+            flash_color: Color::srgb(1.0, 0.2, 0.2),
+            flash_interval_secs: 0.08,
+            screen_shake: true,
+            shake_strength: 8.0,
+            shake_secs: 0.25,
+            knockback_speed: 600.0,
+            knockback_secs: 0.1,
+        }
+    }
+}
+
+/// Gets hit feedback when damaged. Only the first hit in a step counts, the rest are
+/// ignored (see apply_damage)
+#[derive(Component)]
+pub struct HitReaction;
+
+/// Written by apply_damage when an entity with HitReaction actually takes damage
+#[derive(Message)]
+pub struct HitTaken {
+    pub target: Entity,
+    /// Health actually lost, can be less than the hit if it was the killing blow
+    pub amount: u32,
+}
+
+/// Where a hit came from, for knockback. Only does anything if the hit got through
+#[derive(Message)]
+pub struct KnockbackEvent {
+    pub target: Entity,
+    pub from: Vec2,
+}
+
+/// Flashing between flash_color and the sprite's own color until the timer runs out
+#[derive(Component)]
+pub struct HitFlash(pub Timer);
+
+/// Being pushed along `velocity` (pixels per second) on top of normal movement
+#[derive(Component)]
+pub struct Knockback {
+    pub velocity: Vec2,
+    pub timer: Timer,
+}
+
+fn react_to_hits(
+    mut commands: Commands,
+    mut hits: MessageReader<HitTaken>,
+    mut knockbacks: MessageReader<KnockbackEvent>,
+    targets: Query<&Transform>,
+    mut camera: Single<&mut CameraShake, With<GameCamera>>,
+    settings: Res<HitFeedbackSettings>,
+) {
+    let hit: Vec<Entity> = hits.read().map(|hit| hit.target).collect();
+    let knockbacks: Vec<&KnockbackEvent> = knockbacks.read().collect();
+
+    for target in hit {
+        make_invulnerable(&mut commands, target, settings.iframe_secs);
+        commands.entity(target).insert(HitFlash(Timer::from_seconds(settings.iframe_secs, TimerMode::Once)));
+
+        if settings.screen_shake {
+            camera.start(settings.shake_strength, settings.shake_secs);
+        }
+
+        // first knockback for this target wins
+        if let Some(knockback) = knockbacks.iter().find(|knockback| knockback.target == target)
+            && let Ok(transform) = targets.get(target)
+        {
+            let away = (transform.translation.truncate() - knockback.from).normalize_or_zero();
+            commands.entity(target).insert(Knockback {
+                velocity: away * settings.knockback_speed,
+                timer: Timer::from_seconds(settings.knockback_secs, TimerMode::Once),
+            });
+        }
+    }
+}
+
+fn apply_knockback(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Transform, &mut Knockback)>,
+) {
+    for (entity, mut transform, mut knockback) in &mut query {
+        let delta = knockback.velocity * time.delta_secs();
+        transform.translation.x += delta.x;
+        transform.translation.y += delta.y;
+
+        knockback.timer.tick(time.delta());
+        if knockback.timer.is_finished() {
+            commands.entity(entity).remove::<Knockback>();
+        }
+    }
+}
+
+// visual only, so it runs every frame instead of on the fixed step
+fn flash_hit_sprites(
+    mut commands: Commands,
+    time: Res<Time>,
+    settings: Res<HitFeedbackSettings>,
+    mut query: Query<(Entity, &AsciiSprite, &mut TextColor, &mut HitFlash)>,
+) {
+    for (entity, sprite, mut color, mut flash) in &mut query {
+        flash.0.tick(time.delta());
+        if flash.0.is_finished() {
+            color.0 = sprite.color;
+            commands.entity(entity).remove::<HitFlash>();
+            continue;
+        }
+
+        let flash_on = ((flash.0.elapsed_secs() / settings.flash_interval_secs) as u32).is_multiple_of(2);
+        color.0 = if flash_on { settings.flash_color } else { sprite.color };
+    }
+}
+
+pub struct HitFeedbackPlugin;
+
+impl Plugin for HitFeedbackPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<HitFeedbackSettings>()
+            .add_message::<HitTaken>()
+            .add_message::<KnockbackEvent>()
+            .add_systems(FixedUpdate, react_to_hits
+                .after(apply_damage)
+                // before the player can be despawned by apply_death
+                .before(apply_death)
+                .in_set(SimulationSet::Damage)
+                .run_if(in_state(GameState::Playing)))
+            .add_systems(FixedUpdate, apply_knockback
+                .in_set(SimulationSet::Movement)
+                .run_if(in_state(GameState::Playing)))
+            .add_systems(Update, flash_hit_sprites.run_if(in_state(GameState::Playing)));
+    }
+}
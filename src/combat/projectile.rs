@@ -1,6 +1,8 @@
 use bevy::prelude::*;
-use crate::physics::{CircleHitBox, Velocity};
+use crate::physics::{Collider, SimulationSet, Velocity, apply_velocity};
 use super::damage::ProjectileDamage;
+use super::status::{Element, Elemental};
+use super::weapon::WeaponId;
 
 #[derive(Component)]
 pub struct Lifetimer(pub Timer);
@@ -12,28 +14,53 @@ pub struct ProjectileConfig{
     pub font_size: f32,
     pub speed: f32,
     pub damage: u32,
+    /// Width of the square sprite, the hitbox is a circle inside it
+    pub size: f32,
+    pub lifetime_secs: f32,
+    /// How many extra enemies it can pass through
+    pub pierce: u32,
+    /// Status effect applied to what it hits (player shots only for now)
+    pub element: Option<Element>,
 }
 
 #[derive(Component)]
 pub struct Projectile;
 
+/// Where a projectile was before this fixed step moved it. Collision sweeps from here to
+/// the current position so fast projectiles can't tunnel through things
+#[derive(Component)]
+pub struct PreviousPosition(pub Vec2);
+
 #[derive(Component)]
 pub struct PlayerOwned;
 
 #[derive(Component)]
 pub struct EnemyOwned;
 
+/// Which weapon fired a player projectile (used for run stats). Turret shots have none
+#[derive(Component, Clone, Copy)]
+pub struct SourceWeapon(pub WeaponId);
+
+/// Player projectile that keeps going after a hit. Remembers what it already hit so it
+/// doesn't damage the same drone again on the next step
+#[derive(Component)]
+pub struct Pierce {
+    pub remaining: u32,
+    pub already_hit: Vec<Entity>,
+}
+
 pub fn spawn_player_projectile(
     commands: &mut Commands,
     position: Vec2,
     direction: Vec2,
     config: &ProjectileConfig,
-) {
-    commands.spawn((
+    weapon_id: Option<WeaponId>,
+) -> Entity {
+    let mut projectile = commands.spawn((
         Transform::from_translation(position.extend(0.0)),
-        Sprite { //hard coded square for now
+        Sprite {
           color: config.color,
-          custom_size: Some(Vec2::new(4.0, 4.0)),
+          custom_size: Some(Vec2::splat(config.size)),
           ..default()
         },
         Velocity {
@@ -42,10 +69,25 @@ pub fn spawn_player_projectile(
         },
         ProjectileDamage(config.damage),
         Projectile,
+        PreviousPosition(position),
         PlayerOwned, //THis makes this function only work for the player
-        Lifetimer(Timer::from_seconds(2.0, TimerMode::Once)),
-        CircleHitBox{ radius: 2.0},
+        Pierce { remaining: config.pierce, already_hit: Vec::new() },
+        Lifetimer(Timer::from_seconds(config.lifetime_secs, TimerMode::Once)),
+        Collider::circle(config.size / 2.0),
     ));
+    if let Some(weapon_id) = weapon_id {
+        projectile.insert(SourceWeapon(weapon_id));
+    }
+    if let Some(element) = config.element {
+        projectile.insert(Elemental(element));
+    }
+    projectile.id()
+}
+
+fn store_previous_positions(mut query: Query<(&Transform, &mut PreviousPosition)>) {
+    for (transform, mut previous) in &mut query {
+        previous.0 = transform.translation.truncate();
+    }
 }
 
 fn tick_lifetimes(
@@ -66,12 +108,12 @@ pub fn spawn_enemy_projectile(
     position: Vec2,
     direction: Vec2,
     config: &ProjectileConfig,
-) {
+) -> Entity {
     commands.spawn((
         Transform::from_translation(position.extend(0.0)),
         Sprite {
             color: config.color,
-            custom_size: Some(Vec2::new(4.0, 4.0)),
+            custom_size: Some(Vec2::splat(config.size)),
             ..default()
         },
         Velocity {
@@ -80,10 +122,11 @@ pub fn spawn_enemy_projectile(
         },
         ProjectileDamage(config.damage),
         Projectile,
+        PreviousPosition(position),
         EnemyOwned,
-        Lifetimer(Timer::from_seconds(2.0, TimerMode::Once)),
-        CircleHitBox { radius: 2.0 },
-    ));
+        Lifetimer(Timer::from_seconds(config.lifetime_secs, TimerMode::Once)),
+        Collider::circle(config.size / 2.0),
+    )).id()
 }
 
 pub struct ProjectilePlugin;
@@ -91,22 +134,15 @@ pub struct ProjectilePlugin;
 impl Plugin for ProjectilePlugin {
     fn build(&self, app: &mut App) {
         // Projectiles spawned via spawn_projectile()
-        // Collision systems will be added here later
-        app.add_systems(Update, tick_lifetimes);
+        // Collision systems live in collision.rs
+        app.add_systems(FixedUpdate, (
+            store_previous_positions.before(apply_velocity),
+            tick_lifetimes,
+        ).in_set(SimulationSet::Movement));
     }
 }
 
 impl ProjectileConfig {
-    pub fn player_bullet() -> Self {
-        Self {
-            projectileShape: "()".to_string(),
-            color: Color::WHITE,
-            font_size: 24.0,
-            speed: 1000.0,
-            damage: 1,
-        }
-    }
-
     pub fn enemy_bullet() -> Self {
         Self {
             projectileShape: "o".to_string(),
@@ -114,6 +150,10 @@ impl ProjectileConfig {
             font_size: 24.0,
             speed: 400.0,
             damage: 1,
+            size: 4.0,
+            lifetime_secs: 2.0,
+            pierce: 0,
+            element: None,
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,144 @@
+//Status effects left behind by elemental shots (weapon modifiers)
+use bevy::prelude::*;
+use crate::physics::{SimulationSet, Velocity, apply_velocity};
+use crate::state::GameState;
+use super::damage::{DamageEvent, apply_damage};
+
+const BURN_TICKS: u32 = 3;
+const BURN_TICK_SECS: f32 = 0.5;
+const BURN_DAMAGE: u32 = 1;
+
+const CHILL_SECS: f32 = 2.0;
+/// Chilled things move at this fraction of their speed
+const CHILL_SPEED_FACTOR: f32 = 0.5;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Element {
+    /// Damage over time
+    Burn,
+    /// Slows movement
+    Frost,
+}
+
+/// On a player projectile: what it does to whatever it hits (beams carry theirs in Beam)
+#[derive(Component, Clone, Copy)]
+pub struct Elemental(pub Element);
+
+/// Takes BURN_DAMAGE every BURN_TICK_SECS until ticks_left runs out
+#[derive(Component)]
+pub struct Burning {
+    pub tick: Timer,
+    pub ticks_left: u32,
+}
+
+/// Can't move or shoot until the timer runs out (EMP)
+#[derive(Component)]
+pub struct Stunned(pub Timer);
+
+/// Moving at CHILL_SPEED_FACTOR speed until the timer runs out
+#[derive(Component)]
+pub struct Chilled {
+    pub timer: Timer,
+    /// Whether the speed has been lowered yet (done by update_chilled, which also undoes it)
+    slowed: bool,
+}
+
+/// Hitting something that's already burning / chilled restarts the effect instead of stacking
+pub fn apply_element(commands: &mut Commands, target: Entity, element: Element) {
+    match element {
+        Element::Burn => {
+            commands.entity(target).entry::<Burning>()
+                .and_modify(|mut burning| burning.ticks_left = BURN_TICKS)
+                .or_insert(Burning {
+                    tick: Timer::from_seconds(BURN_TICK_SECS, TimerMode::Repeating),
+                    ticks_left: BURN_TICKS,
+                });
+        }
+        Element::Frost => {
+            commands.entity(target).entry::<Chilled>()
+                .and_modify(|mut chilled| chilled.timer.reset())
+                .or_insert(Chilled {
+                    timer: Timer::from_seconds(CHILL_SECS, TimerMode::Once),
+                    slowed: false,
+                });
+        }
+    }
+}
+
+/// Stuns `target` for `secs`. Restarts the stun if it's already stunned
+pub fn apply_stun(commands: &mut Commands, target: Entity, secs: f32) {
+    commands.entity(target).insert(Stunned(Timer::from_seconds(secs, TimerMode::Once)));
+}
+
+fn update_burning(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut damage_messages: MessageWriter<DamageEvent>,
+    mut query: Query<(Entity, &mut Burning)>,
+) {
+    for (entity, mut burning) in &mut query {
+        burning.tick.tick(time.delta());
+        if !burning.tick.just_finished() {
+            continue;
+        }
+
+        damage_messages.write(DamageEvent { target: entity, amount: BURN_DAMAGE });
+        burning.ticks_left = burning.ticks_left.saturating_sub(1);
+        if burning.ticks_left == 0 {
+            commands.entity(entity).remove::<Burning>();
+        }
+    }
+}
+
+// only things with a Velocity slow down (drones), the player shrugs it off
+fn update_chilled(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Chilled, &mut Velocity)>,
+) {
+    for (entity, mut chilled, mut velocity) in &mut query {
+        if !chilled.slowed {
+            velocity.speed *= CHILL_SPEED_FACTOR;
+            chilled.slowed = true;
+        }
+
+        chilled.timer.tick(time.delta());
+        if chilled.timer.is_finished() {
+            velocity.speed /= CHILL_SPEED_FACTOR;
+            commands.entity(entity).remove::<Chilled>();
+        }
+    }
+}
+
+// what a stun stops is up to the systems it affects (apply_velocity, shoot_at_target)
+fn update_stunned(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Stunned)>,
+) {
+    for (entity, mut stunned) in &mut query {
+        stunned.0.tick(time.delta());
+        if stunned.0.is_finished() {
+            commands.entity(entity).remove::<Stunned>();
+        }
+    }
+}
+
+pub struct StatusPlugin;
+
+impl Plugin for StatusPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(FixedUpdate, update_chilled
+                .before(apply_velocity)
+                .in_set(SimulationSet::Movement)
+                .run_if(in_state(GameState::Playing)))
+            .add_systems(FixedUpdate, update_stunned
+                .in_set(SimulationSet::Intent)
+                .run_if(in_state(GameState::Playing)))
+            .add_systems(FixedUpdate, update_burning
+                .before(apply_damage)
+                .in_set(SimulationSet::Damage)
+                .run_if(in_state(GameState::Playing)));
+    }
+}
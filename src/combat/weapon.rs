@@ -1,91 +1,564 @@
+use std::collections::HashMap;
+use std::fs;
 use std::time::Duration;
 use bevy::prelude::*;
+use super::fire_pattern::FirePattern;
 use super::projectile::ProjectileConfig;
+use super::status::Element;
 
-/// Describes how a weapon fires its projectiles
+/// Weapons are defined here, the top of the file describes the format.
+/// Relative to the working directory, like run_history.txt
+pub const WEAPONS_PATH: &str = "assets/weapons.txt";
+
+/// Upgrades a single weapon can hold
+pub const MAX_MODIFIERS: usize = 3;
+
+/// Swap delay for weapons that don't set one
+const DEFAULT_SWAP_SECS: f32 = 0.25;
+
+/// Copy of the weapons file baked into the binary, used when WEAPONS_PATH can't be read
+const BUILT_IN_WEAPONS: &str = include_str!("../../assets/weapons.txt");
+
+/// A weapon's `[id]` from the weapons file
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct WeaponId(&'static str);
+
+impl WeaponId {
+    // Weapons the code hands out itself (starting weapon, loadouts, recipes).
+    // Everything else only exists in the weapons file
+    pub const PISTOL: WeaponId = WeaponId("pistol");
+    pub const SHOTGUN: WeaponId = WeaponId("shotgun");
+    /// Fired by turrets, not carried
+    pub const TURRET: WeaponId = WeaponId("turret");
+
+    pub fn as_str(&self) -> &'static str {
+        self.0
+    }
+}
+
+/// One weapon from the weapons file
+pub struct WeaponDefinition {
+    pub id: WeaponId,
+    pub name: String,
+    /// Shown in the weapon hotbar
+    pub glyph: String,
+    pub color: Color,
+    pub fire_cooldown_secs: f32,
+    pub fire_pattern: FirePattern,
+    pub projectile: ProjectileConfig,
+    /// Shots per magazine (a spread counts as one shot)
+    pub magazine_size: u32,
+    pub reload_secs: f32,
+    /// Reserve ammo the weapon comes with, None for unlimited
+    pub starting_reserve: Option<u32>,
+    /// Seconds after switching to it before it can fire
+    pub swap_secs: f32,
+}
+
+/// Every weapon in the game, loaded from WEAPONS_PATH at startup
+#[derive(Resource)]
+pub struct WeaponDefinitions {
+    /// In file order
+    weapons: Vec<WeaponDefinition>,
+}
+
+impl WeaponDefinitions {
+    /// Reads WEAPONS_PATH, falling back to the built-in copy if it's missing or has no pistol
+    /// (every run starts with one). Broken weapons are logged and left out.
+    pub fn load() -> Self {
+        let loaded = match fs::read_to_string(WEAPONS_PATH) {
+            Ok(text) => Some(Self::parse(&text)),
+            Err(err) => {
+                warn!("Couldn't read {}: {}, using built-in weapons", WEAPONS_PATH, err);
+                None
+            }
+        };
+
+        match loaded {
+            Some(weapons) if weapons.get(WeaponId::PISTOL).is_some() => {
+                info!("Loaded {} weapons from {}", weapons.weapons.len(), WEAPONS_PATH);
+                weapons
+            }
+            Some(_) => {
+                error!("{} has no [{}] weapon, using built-in weapons", WEAPONS_PATH, WeaponId::PISTOL.as_str());
+                Self::parse(BUILT_IN_WEAPONS)
+            }
+            None => Self::parse(BUILT_IN_WEAPONS),
+        }
+    }
+
+    /// `[id]` starts a weapon, `key = value` lines fill it in, `#` starts a comment
+    pub fn parse(text: &str) -> Self {
+        let mut sections: Vec<(&str, HashMap<&str, &str>)> = Vec::new();
+
+        for (line_number, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(id) = line.strip_prefix('[').and_then(|rest| rest.strip_suffix(']')) {
+                sections.push((id.trim(), HashMap::new()));
+            } else if let (Some((key, value)), Some((_, fields))) = (line.split_once('='), sections.last_mut()) {
+                fields.insert(key.trim(), value.trim());
+            } else {
+                warn!("weapons line {}: expected [id] or key = value, got '{}'", line_number + 1, line);
+            }
+        }
+
+        let mut weapons: Vec<WeaponDefinition> = Vec::new();
+        for (id, fields) in sections {
+            if weapons.iter().any(|weapon| weapon.id.as_str() == id) {
+                warn!("Weapon [{}] is defined twice, keeping the first one", id);
+                continue;
+            }
+            match WeaponDefinition::from_fields(id, &fields) {
+                Ok(weapon) => weapons.push(weapon),
+                Err(err) => warn!("Skipping weapon [{}]: {}", id, err),
+            }
+        }
+
+        Self { weapons }
+    }
+
+    pub fn get(&self, id: WeaponId) -> Option<&WeaponDefinition> {
+        self.weapons.iter().find(|weapon| weapon.id == id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &WeaponDefinition> {
+        self.weapons.iter()
+    }
+
+    /// Display name, or the id if the weapon isn't defined
+    pub fn name(&self, id: WeaponId) -> &str {
+        self.get(id).map_or(id.as_str(), |weapon| weapon.name.as_str())
+    }
+}
+
+/// `key` parsed as T, None if it isn't there
+fn field<T: std::str::FromStr>(fields: &HashMap<&str, &str>, key: &str) -> Result<Option<T>, String> {
+    fields.get(key)
+        .map(|value| value.parse().map_err(|_| format!("bad value for {}: '{}'", key, value)))
+        .transpose()
+}
+
+fn required<T: std::str::FromStr>(fields: &HashMap<&str, &str>, key: &str) -> Result<T, String> {
+    field(fields, key)?.ok_or(format!("missing {}", key))
+}
+
+/// A duration, 0 or more
+fn seconds(key: &str, value: f32) -> Result<f32, String> {
+    if value.is_finite() && value >= 0.0 {
+        Ok(value)
+    } else {
+        Err(format!("bad value for {}: '{}', expected 0 or more seconds", key, value))
+    }
+}
+
+fn positive(key: &str, value: f32) -> Result<f32, String> {
+    if value.is_finite() && value > 0.0 {
+        Ok(value)
+    } else {
+        Err(format!("bad value for {}: '{}', expected more than 0", key, value))
+    }
+}
+
+/// A scale factor, 1 or more
+fn multiplier(key: &str, value: f32) -> Result<f32, String> {
+    if value.is_finite() && value >= 1.0 {
+        Ok(value)
+    } else {
+        Err(format!("bad value for {}: '{}', expected 1 or more", key, value))
+    }
+}
+
+/// Projectiles per trigger pull for spread, burst and orbit
+fn count_field(fields: &HashMap<&str, &str>) -> Result<usize, String> {
+    match required(fields, "count")? {
+        0 => Err("count must be at least 1".to_string()),
+        count => Ok(count),
+    }
+}
+
+/// "r g b", 0.0 - 1.0
+fn color_field(fields: &HashMap<&str, &str>, key: &str) -> Result<Option<Color>, String> {
+    let Some(value) = fields.get(key) else { return Ok(None) };
+    let channels: Vec<f32> = value.split_whitespace().filter_map(|channel| channel.parse().ok()).collect();
+    match channels[..] {
+        [r, g, b] => Ok(Some(Color::srgb(r, g, b))),
+        _ => Err(format!("bad value for {}: '{}', expected r g b", key, value)),
+    }
+}
+
+impl WeaponDefinition {
+    fn from_fields(id: &str, fields: &HashMap<&str, &str>) -> Result<Self, String> {
+        let name: String = required(fields, "name")?;
+
+        let fire_pattern = match fields.get("pattern").copied().unwrap_or("single") {
+            "single" => FirePattern::Single,
+            "spread" => FirePattern::Spread {
+                count: count_field(fields)?,
+                angle_degrees: required(fields, "spread")?,
+            },
+            "burst" => FirePattern::Burst {
+                count: count_field(fields)?,
+                interval: seconds("interval", required(fields, "interval")?)?,
+            },
+            "beam" => FirePattern::Beam {
+                range: positive("range", required(fields, "range")?)?,
+                width: field(fields, "width")?.unwrap_or(4.0),
+            },
+            "charge" => FirePattern::Charge {
+                max_secs: positive("charge_time", required(fields, "charge_time")?)?,
+                max_multiplier: multiplier("charge_multiplier", required(fields, "charge_multiplier")?)?,
+            },
+            "orbit" => FirePattern::Orbit {
+                count: count_field(fields)?,
+                radius: positive("radius", required(fields, "radius")?)?,
+                degrees_per_sec: required(fields, "orbit_speed")?,
+            },
+            other => return Err(format!("unknown pattern '{}'", other)),
+        };
+
+        // beams hit instantly and orbiters circle the player, neither has a projectile speed
+        let speed = match fire_pattern {
+            FirePattern::Beam { .. } | FirePattern::Orbit { .. } => field(fields, "speed")?.unwrap_or(0.0),
+            _ => positive("speed", required(fields, "speed")?)?,
+        };
+
+        let projectile = ProjectileConfig {
+            projectileShape: "o".to_string(),
+            color: color_field(fields, "projectile_color")?.unwrap_or(Color::WHITE),
+            font_size: 24.0,
+            speed,
+            damage: required(fields, "damage")?,
+            size: field(fields, "projectile_size")?.unwrap_or(4.0),
+            lifetime_secs: seconds("lifetime", field(fields, "lifetime")?.unwrap_or(2.0))?,
+            pierce: field(fields, "pierce")?.unwrap_or(0),
+            element: match fields.get("element").copied() {
+                None => None,
+                Some("burn") => Some(Element::Burn),
+                Some("frost") => Some(Element::Frost),
+                Some(other) => return Err(format!("unknown element '{}'", other)),
+            },
+        };
+
+        Ok(Self {
+            // loaded once at startup and kept for the whole game, leaking keeps WeaponId Copy
+            id: WeaponId(Box::leak(id.to_string().into_boxed_str())),
+            glyph: field(fields, "glyph")?.unwrap_or_else(|| name.chars().take(1).collect()),
+            name,
+            color: color_field(fields, "color")?.unwrap_or(Color::WHITE),
+            fire_cooldown_secs: seconds("cooldown", required(fields, "cooldown")?)?,
+            fire_pattern,
+            projectile,
+            magazine_size: match required(fields, "magazine")? {
+                0 => return Err("magazine must be at least 1".to_string()),
+                size => size,
+            },
+            reload_secs: seconds("reload", required(fields, "reload")?)?,
+            starting_reserve: field(fields, "reserve")?,
+            swap_secs: seconds("swap", field(fields, "swap")?.unwrap_or(DEFAULT_SWAP_SECS))?,
+        })
+    }
+}
+
+/// Magazine and reserve of one Weapon
 #[derive(Clone, Copy)]
-pub enum FirePattern {
-    /// Single projectile straight ahead
-    Single,
-    /// Multiple projectiles in a spread (count, total spread angle in degrees)
-    Spread { count: usize, angle_degrees: f32 },
+pub struct WeaponAmmo {
+    pub magazine: u32,
+    /// None for unlimited
+    pub reserve: Option<u32>,
+}
+
+impl WeaponAmmo {
+    /// Full magazine plus the starting reserve
+    pub fn full(definition: &WeaponDefinition) -> Self {
+        Self {
+            magazine: definition.magazine_size,
+            reserve: definition.starting_reserve,
+        }
+    }
+
+    pub fn can_reload(&self, magazine_size: u32) -> bool {
+        self.magazine < magazine_size && self.reserve != Some(0)
+    }
+
+    /// Tops the magazine up from the reserve
+    pub fn reload(&mut self, magazine_size: u32) {
+        let needed = magazine_size.saturating_sub(self.magazine);
+        let taken = match self.reserve.as_mut() {
+            Some(reserve) => {
+                let taken = needed.min(*reserve);
+                *reserve -= taken;
+                taken
+            }
+            None => needed,
+        };
+        self.magazine += taken;
+    }
+
+    /// Does nothing for unlimited reserves
+    pub fn add_reserve(&mut self, rounds: u32) {
+        if let Some(reserve) = self.reserve.as_mut() {
+            *reserve += rounds;
+        }
+    }
 }
 
-/// The active weapon on an entity — built from a WeaponType
-#[derive(Component)]
+/// One weapon the player owns, built from a WeaponDefinition. Each inventory slot keeps
+/// its own so cooldowns, ammo etc. survive switching weapons (and being dropped)
+#[derive(Clone)]
 pub struct Weapon {
-    pub weapon_type: WeaponType,
+    pub weapon_id: WeaponId,
     pub config: ProjectileConfig,
     pub fire_cooldown: Timer,
     pub fire_pattern: FirePattern,
+    pub ammo: WeaponAmmo,
+    pub magazine_size: u32,
+    pub reload_secs: f32,
+    /// Some while reloading, the magazine is filled when it finishes
+    pub reload: Option<Timer>,
+    /// Seconds the trigger has been held, for Charge weapons
+    pub charge: Option<f32>,
+    /// Damage a held Beam has built up but not dealt yet, always under 1
+    pub beam_damage: f32,
+    /// Seconds after switching to this weapon before it can fire
+    pub swap_secs: f32,
+    /// Applied upgrades, in the order they were crafted
+    pub modifiers: Vec<WeaponModifier>,
+}
+
+/// Crafted upgrade for the weapon in hand
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum WeaponModifier {
+    /// +1 damage
+    Damage,
+    /// 20% shorter cooldown
+    FireRate,
+    /// +1 projectile per shot, a single shot becomes a narrow spread
+    ExtraPellet,
+    /// Passes through one more drone
+    Pierce,
+    /// Shots leave a status effect. Only one element per weapon
+    Elemental(Element),
+}
+
+impl WeaponModifier {
+    pub fn name(&self) -> &'static str {
+        match self {
+            WeaponModifier::Damage => "Damage +1",
+            WeaponModifier::FireRate => "Fire Rate +20%",
+            WeaponModifier::ExtraPellet => "Extra Pellet",
+            WeaponModifier::Pierce => "Pierce +1",
+            WeaponModifier::Elemental(Element::Burn) => "Incendiary",
+            WeaponModifier::Elemental(Element::Frost) => "Cryo",
+        }
+    }
 }
 
 impl Weapon {
-    pub fn from_type(weapon_type: WeaponType) -> Self {
+    pub fn from_definition(definition: &WeaponDefinition) -> Self {
         Self {
-            weapon_type,
-            config: weapon_type.projectile_config(),
+            weapon_id: definition.id,
+            config: definition.projectile.clone(),
             fire_cooldown: Timer::new(
-                Duration::from_secs_f32(weapon_type.fire_cooldown_secs()),
+                Duration::from_secs_f32(definition.fire_cooldown_secs),
                 TimerMode::Repeating,
             ),
-            fire_pattern: weapon_type.fire_pattern(),
+            fire_pattern: definition.fire_pattern,
+            ammo: WeaponAmmo::full(definition),
+            magazine_size: definition.magazine_size,
+            reload_secs: definition.reload_secs,
+            reload: None,
+            charge: None,
+            beam_damage: 0.0,
+            swap_secs: definition.swap_secs,
+            modifiers: Vec::new(),
         }
     }
-}
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash)]
-pub enum WeaponType {
-    Pistol,
-    Shotgun,
-}
+    /// Starts reloading unless already reloading, the magazine is full or there's nothing
+    /// left in reserve. Returns whether a reload started
+    pub fn start_reload(&mut self) -> bool {
+        if self.reload.is_some() || !self.ammo.can_reload(self.magazine_size) {
+            return false;
+        }
+        self.reload = Some(Timer::from_seconds(self.reload_secs, TimerMode::Once));
+        true
+    }
 
-impl WeaponType {
-    pub fn name(&self) -> &'static str {
-        match self {
-            WeaponType::Pistol => "Pistol",
-            WeaponType::Shotgun => "Shotgun",
+    /// Why `modifier` can't go on this weapon, None if it can
+    pub fn modifier_blocked(&self, modifier: WeaponModifier) -> Option<&'static str> {
+        if self.modifiers.len() >= MAX_MODIFIERS {
+            return Some("no upgrade slots left");
+        }
+        match modifier {
+            WeaponModifier::ExtraPellet if matches!(self.fire_pattern, FirePattern::Beam { .. } | FirePattern::Charge { .. }) => {
+                Some("fires a single beam / charge shot")
+            }
+            WeaponModifier::Elemental(_) if self.config.element.is_some() => Some("already elemental"),
+            _ => None,
         }
     }
 
-    pub fn glyph(&self) -> &'static str {
-        match self {
-            WeaponType::Pistol => "P",
-            WeaponType::Shotgun => "S",
+    /// Applies the modifier if it fits (see modifier_blocked). Returns whether it was applied
+    pub fn apply_modifier(&mut self, modifier: WeaponModifier) -> bool {
+        if self.modifier_blocked(modifier).is_some() {
+            return false;
+        }
+
+        match modifier {
+            WeaponModifier::Damage => self.config.damage += 1,
+            WeaponModifier::FireRate => {
+                let faster = self.fire_cooldown.duration().mul_f32(0.8);
+                self.fire_cooldown.set_duration(faster);
+            }
+            WeaponModifier::ExtraPellet => {
+                self.fire_pattern = match self.fire_pattern {
+                    FirePattern::Single => FirePattern::Spread { count: 2, angle_degrees: 10.0 },
+                    FirePattern::Spread { count, angle_degrees } => FirePattern::Spread { count: count + 1, angle_degrees },
+                    FirePattern::Burst { count, interval } => FirePattern::Burst { count: count + 1, interval },
+                    FirePattern::Orbit { count, radius, degrees_per_sec } => FirePattern::Orbit { count: count + 1, radius, degrees_per_sec },
+                    other => other,
+                };
+            }
+            WeaponModifier::Pierce => self.config.pierce += 1,
+            WeaponModifier::Elemental(element) => self.config.element = Some(element),
         }
+        self.modifiers.push(modifier);
+        true
     }
 
-    pub fn color(&self) -> Color {
-        match self {
-            WeaponType::Pistol => Color::WHITE,
-            WeaponType::Shotgun => Color::WHITE,
+    /// Putting the weapon away cancels a reload or charge in progress
+    pub fn holster(&mut self) {
+        self.reload = None;
+        self.charge = None;
+        self.beam_damage = 0.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PISTOL: &str = "[pistol]\nname = Pistol\ncooldown = 0.3\nmagazine = 12\nreload = 1.0\nspeed = 1000\ndamage = 1\n";
+
+    /// The pistol with one more line, `key = value` replaces the pistol's own if it has one
+    fn pistol_with(line: &str) -> WeaponDefinitions {
+        let key = line.split('=').next().unwrap().trim();
+        let kept: Vec<&str> = PISTOL.lines().filter(|existing| !existing.starts_with(key)).collect();
+        WeaponDefinitions::parse(&format!("{}\n{}\n", kept.join("\n"), line))
+    }
+
+    #[test]
+    fn parses_fields_and_defaults() {
+        let weapons = WeaponDefinitions::parse(PISTOL);
+        let pistol = weapons.get(WeaponId::PISTOL).unwrap();
+        assert_eq!(pistol.name, "Pistol");
+        assert_eq!(pistol.glyph, "P");
+        assert_eq!(pistol.fire_cooldown_secs, 0.3);
+        assert!(matches!(pistol.fire_pattern, FirePattern::Single));
+        assert_eq!(pistol.magazine_size, 12);
+        assert_eq!(pistol.starting_reserve, None);
+        assert_eq!(pistol.swap_secs, DEFAULT_SWAP_SECS);
+        assert_eq!(pistol.projectile.speed, 1000.0);
+        assert_eq!(pistol.projectile.lifetime_secs, 2.0);
+    }
+
+    #[test]
+    fn built_in_weapons_all_load() {
+        let sections = BUILT_IN_WEAPONS.lines().filter(|line| line.trim_start().starts_with('[')).count();
+        let weapons = WeaponDefinitions::parse(BUILT_IN_WEAPONS);
+        assert_eq!(weapons.iter().count(), sections);
+        assert!(weapons.get(WeaponId::PISTOL).is_some());
+        assert!(weapons.get(WeaponId::TURRET).is_some());
+    }
+
+    #[test]
+    fn comments_and_junk_lines_are_ignored() {
+        let weapons = WeaponDefinitions::parse(&format!("# header\nstray line\n{}glyph = Q # the glyph\n", PISTOL));
+        assert_eq!(weapons.get(WeaponId::PISTOL).unwrap().glyph, "Q");
+    }
+
+    #[test]
+    fn missing_or_bad_fields_skip_the_weapon() {
+        let no_damage = PISTOL.replace("damage = 1\n", "");
+        assert!(WeaponDefinitions::parse(&no_damage).get(WeaponId::PISTOL).is_none());
+        assert!(pistol_with("cooldown = fast").get(WeaponId::PISTOL).is_none());
+        assert!(pistol_with("pattern = laser").get(WeaponId::PISTOL).is_none());
+        assert!(pistol_with("element = poison").get(WeaponId::PISTOL).is_none());
+        assert!(pistol_with("color = 1 1").get(WeaponId::PISTOL).is_none());
+    }
+
+    #[test]
+    fn negative_or_non_finite_durations_are_rejected() {
+        for key in ["cooldown", "reload", "lifetime", "swap"] {
+            for value in ["-1", "NaN", "inf"] {
+                let weapons = pistol_with(&format!("{} = {}", key, value));
+                assert!(weapons.get(WeaponId::PISTOL).is_none(), "{} = {} was accepted", key, value);
+            }
+        }
+        assert!(pistol_with("swap = 0").get(WeaponId::PISTOL).is_some());
+
+        let charge = format!("{}pattern = charge\ncharge_multiplier = 3\n", PISTOL);
+        for value in ["0", "-0.5", "NaN"] {
+            let weapons = WeaponDefinitions::parse(&format!("{}charge_time = {}\n", charge, value));
+            assert!(weapons.get(WeaponId::PISTOL).is_none(), "charge_time = {} was accepted", value);
         }
     }
 
-    pub fn projectile_config(&self) -> ProjectileConfig {
-        match self {
-            WeaponType::Pistol => ProjectileConfig::player_bullet(),
-            WeaponType::Shotgun => ProjectileConfig {
-                projectileShape: "o".to_string(),
-                color: Color::srgb(1.0, 0.6, 0.0),
-                font_size: 24.0,
-                speed: 700.0,
-                damage: 1,
-            },
+    #[test]
+    fn speed_and_magazine_must_be_positive() {
+        for line in ["speed = 0", "speed = -100", "speed = NaN", "magazine = 0"] {
+            assert!(pistol_with(line).get(WeaponId::PISTOL).is_none(), "{} was accepted", line);
         }
+        // beams don't need a speed
+        let beam = PISTOL.replace("speed = 1000\n", "pattern = beam\nrange = 400\n");
+        assert!(WeaponDefinitions::parse(&beam).get(WeaponId::PISTOL).is_some());
     }
 
-    pub fn fire_cooldown_secs(&self) -> f32 {
-        match self {
-            WeaponType::Pistol => 0.3,
-            WeaponType::Shotgun => 0.6,
+    #[test]
+    fn pattern_counts_must_be_at_least_one() {
+        let patterns = [
+            "pattern = spread\nspread = 30\n",
+            "pattern = burst\ninterval = 0.1\n",
+            "pattern = orbit\nradius = 40\norbit_speed = 180\n",
+        ];
+        for pattern in patterns {
+            let base = format!("{}{}", PISTOL, pattern);
+            assert!(WeaponDefinitions::parse(&format!("{}count = 3\n", base)).get(WeaponId::PISTOL).is_some(), "{}", pattern);
+            assert!(WeaponDefinitions::parse(&format!("{}count = 0\n", base)).get(WeaponId::PISTOL).is_none(), "{}", pattern);
         }
     }
 
-    pub fn fire_pattern(&self) -> FirePattern {
-        match self {
-            WeaponType::Pistol => FirePattern::Single,
-            WeaponType::Shotgun => FirePattern::Spread { count: 4, angle_degrees: 30.0 },
+    #[test]
+    fn charge_multiplier_must_be_at_least_one() {
+        let charge = format!("{}pattern = charge\ncharge_time = 1\n", PISTOL);
+        assert!(WeaponDefinitions::parse(&format!("{}charge_multiplier = 1\n", charge)).get(WeaponId::PISTOL).is_some());
+        for value in ["0.5", "0", "-2", "NaN"] {
+            let weapons = WeaponDefinitions::parse(&format!("{}charge_multiplier = {}\n", charge, value));
+            assert!(weapons.get(WeaponId::PISTOL).is_none(), "charge_multiplier = {} was accepted", value);
+        }
+    }
+
+    #[test]
+    fn beam_range_and_orbit_radius_must_be_positive() {
+        let beam = format!("{}pattern = beam\n", PISTOL);
+        let orbit = format!("{}pattern = orbit\ncount = 2\norbit_speed = 180\n", PISTOL);
+        for value in ["0", "-10", "NaN"] {
+            let weapons = WeaponDefinitions::parse(&format!("{}range = {}\n", beam, value));
+            assert!(weapons.get(WeaponId::PISTOL).is_none(), "range = {} was accepted", value);
+            let weapons = WeaponDefinitions::parse(&format!("{}radius = {}\n", orbit, value));
+            assert!(weapons.get(WeaponId::PISTOL).is_none(), "radius = {} was accepted", value);
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn duplicate_ids_keep_the_first() {
+        let second = PISTOL.replace("name = Pistol", "name = Other Pistol");
+        let weapons = WeaponDefinitions::parse(&format!("{}{}", PISTOL, second));
+        assert_eq!(weapons.iter().count(), 1);
+        assert_eq!(weapons.name(WeaponId::PISTOL), "Pistol");
+    }
+}
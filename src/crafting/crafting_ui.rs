@@ -1,12 +1,14 @@
 use bevy::prelude::*;
 use bevy::ecs::hierarchy::ChildSpawnerCommands;
 
-use crate::crafting::try_craft;
-use crate::inventory::has_resources;
+use crate::combat::WeaponDefinitions;
+use crate::crafting::{can_craft, try_craft, CraftResult};
+use crate::inventory::{active_weapon, has_resources};
 use crate::state::GameState;
 use crate::inventory::Inventory;
 use crate::player::Player;
-use super::recipe::{Recipe, ALL_RECIPES};
+use crate::replay::ReplayButton;
+use super::recipe::{Recipe, RecipeCategory, ALL_RECIPES};
 
 // =============================================================================
 // MARKER COMPONENTS
@@ -59,6 +61,7 @@ pub fn toggle_crafting(
 pub fn spawn_crafting_ui(
     mut commands: Commands,
     player_query: Query<&Inventory, With<Player>>,
+    weapons: Res<WeaponDefinitions>,
 ) {
     let player_inventory = player_query.single().ok();
 
@@ -82,18 +85,41 @@ pub fn spawn_crafting_ui(
             TextColor(Color::WHITE),
         ));
 
-        // Recipe list container
-        parent.spawn((
-            Node {
-                flex_direction: FlexDirection::Column,
-                row_gap: Val::Px(15.0),
-                padding: UiRect::all(Val::Px(20.0)),
-                ..default()
-            },
-        )).with_children(|list| {
-            // Spawn a row for each recipe
-            for (index, recipe) in ALL_RECIPES.iter().enumerate() {
-                spawn_recipe_row(list, index, recipe, player_inventory);
+        // Items, consumables, and upgrades for the weapon in hand on the right
+        parent.spawn(Node {
+            flex_direction: FlexDirection::Row,
+            column_gap: Val::Px(30.0),
+            ..default()
+        }).with_children(|columns| {
+            let upgrade_target = upgrade_target_label(player_inventory, &weapons);
+
+            for category in [RecipeCategory::Item, RecipeCategory::Consumable, RecipeCategory::Upgrade] {
+                columns.spawn((
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        row_gap: Val::Px(15.0),
+                        padding: UiRect::all(Val::Px(20.0)),
+                        ..default()
+                    },
+                )).with_children(|list| {
+                    let header = match category {
+                        RecipeCategory::Item => "ITEMS".to_string(),
+                        RecipeCategory::Consumable => "CONSUMABLES".to_string(),
+                        RecipeCategory::Upgrade => upgrade_target.clone(),
+                    };
+                    list.spawn((
+                        Text::new(header),
+                        TextFont { font_size: 28.0, ..default() },
+                        TextColor(Color::srgb(0.8, 0.8, 0.8)),
+                    ));
+
+                    // Spawn a row for each recipe, indices stay the ALL_RECIPES ones
+                    for (index, recipe) in ALL_RECIPES.iter().enumerate() {
+                        if recipe.category() == category {
+                            spawn_recipe_row(list, index, recipe, player_inventory);
+                        }
+                    }
+                });
             }
         });
 
@@ -101,6 +127,7 @@ pub fn spawn_crafting_ui(
         parent.spawn((
             Button,
             CloseButton,
+            ReplayButton("close_crafting".to_string()),
             Node {
                 width: Val::Px(200.0),
                 height: Val::Px(50.0),
@@ -192,6 +219,7 @@ fn spawn_recipe_row(
             Button,
             CraftButton(index),
             CraftButtonBg(index),
+            ReplayButton(format!("craft{}", index)),
             Node {
                 width: Val::Px(100.0),
                 height: Val::Px(40.0),
@@ -210,17 +238,16 @@ fn spawn_recipe_row(
     });
 }
 
-/// Check if player has enough resources to craft a recipe
+/// Check if player has enough resources to craft a recipe (and the weapon in hand takes it, for upgrades)
 fn check_can_craft(recipe: &Recipe, player_inventory: Option<&Inventory>) -> bool {
-    let Some(inventory) = player_inventory else { return false };
+    player_inventory.is_some_and(|inventory| can_craft(inventory, recipe))
+}
 
-    for (resource_type, required) in recipe.ingredients.iter() {
-        let have = inventory.resource_inventory.get(resource_type).copied().unwrap_or(0);
-        if have < *required {
-            return false;
-        }
+fn upgrade_target_label(player_inventory: Option<&Inventory>, weapons: &WeaponDefinitions) -> String {
+    match player_inventory.and_then(active_weapon) {
+        Some(weapon) => format!("UPGRADES: {}", weapons.name(weapon.weapon_id).to_uppercase()),
+        None => "UPGRADES".to_string(),
     }
-    true
 }
 
 pub fn despawn_crafting_ui(mut commands: Commands, query: Query<Entity, With<CraftingMenu>>) {
@@ -246,7 +273,8 @@ pub fn handle_close_button(
 
 pub fn handle_craft_buttons(
     query: Query<(&Interaction, &CraftButton), Changed<Interaction>>,
-    mut player_inventory: Query<&mut Inventory, With<Player>>
+    mut player_inventory: Query<&mut Inventory, With<Player>>,
+    weapons: Res<WeaponDefinitions>,
 ) {
     for (interaction, craft_button) in &query {
         if *interaction == Interaction::Pressed {
@@ -260,7 +288,13 @@ pub fn handle_craft_buttons(
                 return;
             }
 
-            try_craft(&mut inventory, recipe);
+            match try_craft(&mut inventory, &weapons, recipe) {
+                CraftResult::AlreadyOwned => info!("Already carrying a {}", recipe.name),
+                CraftResult::SlotsFull => info!("No free weapon slot for the {}, drop a weapon first [G]", recipe.name),
+                CraftResult::CannotUpgrade(reason) => info!("Can't apply {}: {}", recipe.name, reason),
+                CraftResult::NoAmmoToRefill => info!("None of your weapons use ammo"),
+                CraftResult::Success | CraftResult::NotEnoughResources => {}
+            }
         }
     }
 }
@@ -291,9 +325,7 @@ pub fn update_crafting_ui(
     // Update craft button colors
     for (mut bg_color, CraftButtonBg(recipe_idx)) in &mut button_bgs {
         let recipe = &ALL_RECIPES[*recipe_idx];
-        let can_craft = has_resources(inventory, recipe.ingredients);
-
-        *bg_color = if can_craft {
+        *bg_color = if can_craft(inventory, recipe) {
             BackgroundColor(Color::srgb(0.2, 0.5, 0.2)) // Green
         } else {
             BackgroundColor(Color::srgb(0.3, 0.3, 0.3)) // Grey
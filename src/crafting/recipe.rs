@@ -2,8 +2,9 @@ use bevy::prelude::*;
 
 use crate::{
     building::PlaceableType,
-    combat::WeaponType,
-    inventory::{Inventory, has_resources, remove_resource, add_weapon, add_placeable},
+    combat::{Element, WeaponDefinitions, WeaponId, WeaponModifier},
+    inventory::{Inventory, has_resources, remove_resource, add_weapon, add_placeable, active_weapon, active_weapon_mut,
+        free_weapon_slot, WeaponSlotError, ConsumableType, add_consumable},
     resources::ResourceType,
 };
 
@@ -11,10 +12,19 @@ use crate::{
 //In the future we may want to look at recipes including more than just resources?
 
 pub enum CraftableItem {
-    Weapon(WeaponType),
+    Weapon(WeaponId),
+    /// Reserve ammo for every weapon that doesn't have unlimited ammo
+    Ammo,
     Beacon,
+    /// Goes in the quick-use bar
+    Consumable(ConsumableType),
+    /// Upgrade for the weapon in the active slot
+    Modifier(WeaponModifier),
 }
 
+/// Magazines worth of reserve ammo an Ammo Pack adds to each weapon
+const AMMO_PACK_MAGAZINES: u32 = 2;
+
 pub struct Recipe {
     pub name: &'static str,
     pub ingredients: &'static [(ResourceType, u32)],
@@ -25,7 +35,43 @@ pub struct Recipe {
 const SHOTGUN_RECIPE: Recipe = Recipe {                
     name: "Shotgun",
     ingredients: &[(ResourceType::DroneWeaponParts, 1), (ResourceType::ScrapMetal, 2)],                              
-    output: CraftableItem::Weapon(WeaponType::Shotgun),
+    output: CraftableItem::Weapon(WeaponId::SHOTGUN),
+};
+
+const AMMO_RECIPE: Recipe = Recipe {
+    name: "Ammo Pack",
+    ingredients: &[(ResourceType::ScrapMetal, 3)],
+    output: CraftableItem::Ammo,
+};
+
+const MEDKIT_RECIPE: Recipe = Recipe {
+    name: "Medkit",
+    ingredients: &[(ResourceType::ScrapMetal, 2), (ResourceType::Circuitry, 2)],
+    output: CraftableItem::Consumable(ConsumableType::Medkit),
+};
+
+const GRENADE_RECIPE: Recipe = Recipe {
+    name: "Grenade",
+    ingredients: &[(ResourceType::ScrapMetal, 2), (ResourceType::DroneWeaponParts, 1)],
+    output: CraftableItem::Consumable(ConsumableType::Grenade),
+};
+
+const SPEED_STIM_RECIPE: Recipe = Recipe {
+    name: "Speed Stim",
+    ingredients: &[(ResourceType::Circuitry, 2)],
+    output: CraftableItem::Consumable(ConsumableType::SpeedStim),
+};
+
+const DAMAGE_STIM_RECIPE: Recipe = Recipe {
+    name: "Damage Stim",
+    ingredients: &[(ResourceType::Circuitry, 2), (ResourceType::DroneWeaponParts, 1)],
+    output: CraftableItem::Consumable(ConsumableType::DamageStim),
+};
+
+const TURRET_KIT_RECIPE: Recipe = Recipe {
+    name: "Turret Kit",
+    ingredients: &[(ResourceType::ScrapMetal, 4), (ResourceType::Circuitry, 2), (ResourceType::DroneWeaponParts, 2)],
+    output: CraftableItem::Consumable(ConsumableType::TurretKit),
 };
 
 const BEACON_RECIPE: Recipe = Recipe {
@@ -34,21 +80,124 @@ const BEACON_RECIPE: Recipe = Recipe {
     output: CraftableItem::Beacon,
 };
 
-pub const ALL_RECIPES: &[Recipe] = &[SHOTGUN_RECIPE, BEACON_RECIPE];
+const DAMAGE_UPGRADE_RECIPE: Recipe = Recipe {
+    name: "Damage Upgrade",
+    ingredients: &[(ResourceType::DroneWeaponParts, 2), (ResourceType::ScrapMetal, 2)],
+    output: CraftableItem::Modifier(WeaponModifier::Damage),
+};
+
+const FIRE_RATE_UPGRADE_RECIPE: Recipe = Recipe {
+    name: "Fire Rate Upgrade",
+    ingredients: &[(ResourceType::Circuitry, 2), (ResourceType::ScrapMetal, 2)],
+    output: CraftableItem::Modifier(WeaponModifier::FireRate),
+};
+
+const EXTRA_PELLET_RECIPE: Recipe = Recipe {
+    name: "Extra Pellet",
+    ingredients: &[(ResourceType::DroneWeaponParts, 2), (ResourceType::ScrapMetal, 3)],
+    output: CraftableItem::Modifier(WeaponModifier::ExtraPellet),
+};
+
+const PIERCING_ROUNDS_RECIPE: Recipe = Recipe {
+    name: "Piercing Rounds",
+    ingredients: &[(ResourceType::DroneWeaponParts, 1), (ResourceType::Circuitry, 2)],
+    output: CraftableItem::Modifier(WeaponModifier::Pierce),
+};
+
+const INCENDIARY_ROUNDS_RECIPE: Recipe = Recipe {
+    name: "Incendiary Rounds",
+    ingredients: &[(ResourceType::Circuitry, 3), (ResourceType::ScrapMetal, 2)],
+    output: CraftableItem::Modifier(WeaponModifier::Elemental(Element::Burn)),
+};
+
+const CRYO_ROUNDS_RECIPE: Recipe = Recipe {
+    name: "Cryo Rounds",
+    ingredients: &[(ResourceType::Circuitry, 3), (ResourceType::DroneWeaponParts, 1)],
+    output: CraftableItem::Modifier(WeaponModifier::Elemental(Element::Frost)),
+};
+
+pub const ALL_RECIPES: &[Recipe] = &[
+    SHOTGUN_RECIPE,
+    AMMO_RECIPE,
+    BEACON_RECIPE,
+    MEDKIT_RECIPE,
+    GRENADE_RECIPE,
+    SPEED_STIM_RECIPE,
+    DAMAGE_STIM_RECIPE,
+    TURRET_KIT_RECIPE,
+    DAMAGE_UPGRADE_RECIPE,
+    FIRE_RATE_UPGRADE_RECIPE,
+    EXTRA_PELLET_RECIPE,
+    PIERCING_ROUNDS_RECIPE,
+    INCENDIARY_ROUNDS_RECIPE,
+    CRYO_ROUNDS_RECIPE,
+];
+
+/// Which column of the crafting menu a recipe is in
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum RecipeCategory {
+    Item,
+    Consumable,
+    Upgrade,
+}
+
+impl Recipe {
+    pub fn category(&self) -> RecipeCategory {
+        match self.output {
+            CraftableItem::Weapon(_) | CraftableItem::Ammo | CraftableItem::Beacon => RecipeCategory::Item,
+            CraftableItem::Consumable(_) => RecipeCategory::Consumable,
+            CraftableItem::Modifier(_) => RecipeCategory::Upgrade,
+        }
+    }
+}
 
 /// Result of attempting to craft
 pub enum CraftResult {
     Success,
     NotEnoughResources,
     AlreadyOwned, // for weapons that can't stack
+    SlotsFull,
+    CannotUpgrade(&'static str),
+    /// Ammo pack with only unlimited-ammo weapons carried
+    NoAmmoToRefill,
+}
+
+/// What would stop the output going into the inventory (no free weapon slot, upgrade
+/// doesn't fit the weapon in hand, no weapon that uses ammo). None if nothing
+pub fn output_blocked(inventory: &Inventory, recipe: &Recipe) -> Option<CraftResult> {
+    match recipe.output {
+        CraftableItem::Weapon(weapon_id) => match free_weapon_slot(inventory, weapon_id) {
+            Ok(_) => None,
+            Err(WeaponSlotError::AlreadyOwned) => Some(CraftResult::AlreadyOwned),
+            Err(WeaponSlotError::SlotsFull) => Some(CraftResult::SlotsFull),
+        },
+        CraftableItem::Modifier(modifier) => match active_weapon(inventory) {
+            Some(weapon) => weapon.modifier_blocked(modifier).map(CraftResult::CannotUpgrade),
+            None => Some(CraftResult::CannotUpgrade("no weapon in hand")),
+        },
+        CraftableItem::Ammo => {
+            let any_reserve = inventory.weapon_slots.iter().flatten().any(|weapon| weapon.ammo.reserve.is_some());
+            (!any_reserve).then_some(CraftResult::NoAmmoToRefill)
+        }
+        CraftableItem::Consumable(_) | CraftableItem::Beacon => None,
+    }
+}
+
+/// Enough resources and somewhere to put the result
+pub fn can_craft(inventory: &Inventory, recipe: &Recipe) -> bool {
+    has_resources(inventory, recipe.ingredients) && output_blocked(inventory, recipe).is_none()
 }
 
 /// Attempt to craft a recipe. Returns whether it succeeded.
-pub fn try_craft(inventory: &mut Inventory, recipe: &Recipe) -> CraftResult {
+pub fn try_craft(inventory: &mut Inventory, weapons: &WeaponDefinitions, recipe: &Recipe) -> CraftResult {
     // Check if we have the resources
     if !has_resources(inventory, recipe.ingredients) {
         return CraftResult::NotEnoughResources;
     }
+    // checked up front so a craft with nowhere to go doesn't eat the resources
+    if let Some(blocked) = output_blocked(inventory, recipe) {
+        return blocked;
+    }
 
     // Deduct resources
     for (resource, count) in recipe.ingredients {
@@ -57,17 +206,31 @@ pub fn try_craft(inventory: &mut Inventory, recipe: &Recipe) -> CraftResult {
 
     // Add the crafted item
     match &recipe.output {
-        CraftableItem::Weapon(weapon_type) => {
-            if add_weapon(inventory, *weapon_type).is_none() {
-                // Already owned - but we already deducted resources, this shouldn't happen
-                // In a real game you'd check this before deducting
-                return CraftResult::AlreadyOwned;
+        CraftableItem::Weapon(weapon_id) => {
+            // output_blocked already made sure there's a free slot
+            add_weapon(inventory, weapons, *weapon_id);
+        }
+        CraftableItem::Ammo => {
+            for weapon in inventory.weapon_slots.iter_mut().flatten() {
+                weapon.ammo.add_reserve(weapon.magazine_size * AMMO_PACK_MAGAZINES);
             }
         }
+        CraftableItem::Consumable(consumable) => {
+            add_consumable(inventory, *consumable, 1);
+            info!("{} crafted! Use it from the quick-use bar [4-8].", consumable.name());
+        }
         CraftableItem::Beacon => {
             add_placeable(inventory, PlaceableType::ExtractionBeacon, 1);
             info!("Beacon crafted! Press B to enter build mode and place it.");
         }
+        CraftableItem::Modifier(modifier) => {
+            let modifier = *modifier;
+            // output_blocked already made sure there's a weapon that takes it
+            if let Some(weapon) = active_weapon_mut(inventory) {
+                weapon.apply_modifier(modifier);
+                info!("{} applied to {}", modifier.name(), weapons.name(weapon.weapon_id));
+            }
+        }
     }
 
     CraftResult::Success
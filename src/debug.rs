@@ -0,0 +1,50 @@
+mod overlay;
+mod console;
+
+use bevy::diagnostic::FrameTimeDiagnosticsPlugin;
+use bevy::input::InputSystems;
+use bevy::prelude::*;
+
+use crate::physics::debug_draw_hitboxes;
+
+pub use overlay::DebugOverlay;
+pub use console::{ConsoleCommand, DebugConsole};
+
+/// Developer tools: **F3** toggles the debug overlay (FPS, entity counts, wave state,
+/// hitboxes and AI intent gizmos), **`** opens the command console.
+/// Only hooked up in debug builds, same as the time scale hotkeys.
+pub struct DebugPlugin;
+
+impl Plugin for DebugPlugin {
+    fn build(&self, app: &mut App) {
+        if !cfg!(debug_assertions) {
+            return;
+        }
+
+        if !app.is_plugin_added::<FrameTimeDiagnosticsPlugin>() {
+            app.add_plugins(FrameTimeDiagnosticsPlugin::default());
+        }
+
+        app
+            .init_resource::<DebugOverlay>()
+            .init_resource::<DebugConsole>()
+            .add_message::<ConsoleCommand>()
+            .add_systems(Update, overlay::toggle_debug_overlay)
+            .add_systems(Update, (
+                overlay::update_debug_overlay,
+                debug_draw_hitboxes,
+                overlay::draw_desired_directions,
+                overlay::draw_target_lines,
+            ).run_if(overlay::overlay_visible))
+            // the console eats keyboard / mouse input before gameplay sees it
+            .add_systems(PreUpdate, (
+                console::toggle_console,
+                console::block_game_input,
+                console::console_text_input,
+            ).chain().after(InputSystems))
+            .add_systems(Update, (
+                console::run_console_commands,
+                console::update_console_text,
+            ).chain());
+    }
+}
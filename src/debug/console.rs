@@ -0,0 +1,331 @@
+use bevy::input::ButtonState;
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::prelude::*;
+
+use crate::combat::{Health, WeaponDefinitions};
+use crate::difficulty::Difficulty;
+use crate::enemy::Enemy;
+use crate::game_fonts::GameFonts;
+use crate::inventory::{Inventory, InventoryItem, give_item};
+use crate::player::Player;
+use crate::spawning::{DroneConfig, DroneKind, WaveState, ring_position, spawn_drone};
+use crate::state::WavePhase;
+
+const CONSOLE_KEY: KeyCode = KeyCode::Backquote;
+
+/// Lines of output kept / shown above the input line
+const LOG_LINES: usize = 12;
+
+/// Console spawns drones this far from the player
+const SPAWN_DISTANCE: f32 = 250.0;
+/// Most drones one spawn command can add, a huge count would hang the game
+const MAX_SPAWN_COUNT: usize = 500;
+
+const HELP: &[&str] = &[
+    "spawn <drone> [count]    drones: chaser, shooter",
+    "give <item> [count]      resources, weapons, placeables or consumables, e.g. give scrap 10, give shotgun",
+    "health <amount>          set player health",
+    "skipwave                 despawn the current wave's drones and end it, not counted as cleared",
+    "clear                    clear this log",
+];
+
+#[derive(Resource, Default)]
+pub struct DebugConsole {
+    pub open: bool,
+    input: String,
+    log: Vec<String>,
+}
+
+impl DebugConsole {
+    fn print(&mut self, line: impl Into<String>) {
+        self.log.push(line.into());
+        if self.log.len() > LOG_LINES {
+            let extra = self.log.len() - LOG_LINES;
+            self.log.drain(..extra);
+        }
+    }
+}
+
+/// A console line that parsed, run by run_console_commands
+#[derive(Message, Clone, Copy)]
+pub enum ConsoleCommand {
+    Help,
+    Spawn { kind: DroneKind, count: usize },
+    Give { item: InventoryItem, count: u32 },
+    SetHealth(u32),
+    SkipWave,
+    Clear,
+}
+
+fn parse_count<T: std::str::FromStr>(arg: Option<&str>, default: T) -> Result<T, String> {
+    match arg {
+        None => Ok(default),
+        Some(arg) => arg.parse().map_err(|_| format!("'{}' isn't a number", arg)),
+    }
+}
+
+impl ConsoleCommand {
+    fn parse(line: &str, weapons: &WeaponDefinitions) -> Result<Self, String> {
+        let mut words = line.split_whitespace();
+        let Some(command) = words.next() else {
+            return Err(String::new());
+        };
+        let arg = words.next();
+        let count_arg = words.next();
+
+        match command.to_lowercase().as_str() {
+            "help" => Ok(ConsoleCommand::Help),
+            "clear" => Ok(ConsoleCommand::Clear),
+            "skipwave" => Ok(ConsoleCommand::SkipWave),
+            "spawn" => {
+                let arg = arg.ok_or("usage: spawn <drone> [count]")?;
+                let kind = DroneKind::ALL.into_iter()
+                    .find(|kind| kind.name().to_lowercase().starts_with(&arg.to_lowercase()))
+                    .ok_or(format!("unknown drone '{}'", arg))?;
+                let count = parse_count(count_arg, 1)?;
+                if count > MAX_SPAWN_COUNT {
+                    return Err(format!("can spawn at most {} drones at once", MAX_SPAWN_COUNT));
+                }
+                Ok(ConsoleCommand::Spawn { kind, count })
+            }
+            "give" => {
+                let arg = arg.ok_or("usage: give <item> [count]")?;
+                let item = InventoryItem::from_name(arg, weapons).ok_or(format!("unknown item '{}'", arg))?;
+                Ok(ConsoleCommand::Give { item, count: parse_count(count_arg, 1)? })
+            }
+            "health" => {
+                let amount = arg
+                    .ok_or("usage: health <amount>")?
+                    .parse()
+                    .map_err(|_| "usage: health <amount>".to_string())?;
+                if amount == 0 {
+                    return Err("health has to be at least 1".to_string());
+                }
+                Ok(ConsoleCommand::SetHealth(amount))
+            }
+            _ => Err(format!("unknown command '{}', try help", command)),
+        }
+    }
+}
+
+// =============================================================================
+// MARKER COMPONENTS
+// =============================================================================
+
+/// Root container for the console (bottom of the screen)
+#[derive(Component)]
+pub struct DebugConsoleRoot;
+
+#[derive(Component)]
+pub struct DebugConsoleText;
+
+// =============================================================================
+// SPAWN / DESPAWN
+// =============================================================================
+
+pub fn toggle_console(
+    mut commands: Commands,
+    input: Res<ButtonInput<KeyCode>>,
+    mut console: ResMut<DebugConsole>,
+) {
+    if !input.just_pressed(CONSOLE_KEY) {
+        return;
+    }
+
+    console.open = !console.open;
+    if console.open {
+        commands.run_system_cached(spawn_console);
+    } else {
+        commands.run_system_cached(despawn_console);
+    }
+}
+
+fn spawn_console(mut commands: Commands, fonts: Res<GameFonts>) {
+    commands.spawn((
+        DebugConsoleRoot,
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(10.0),
+            left: Val::Px(10.0),
+            width: Val::Percent(60.0),
+            padding: UiRect::all(Val::Px(8.0)),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+        GlobalZIndex(100),
+    )).with_children(|parent| {
+        parent.spawn((
+            DebugConsoleText,
+            Text::new(""),
+            TextFont { font: fonts.mono.clone(), font_size: 16.0, ..default() },
+            TextColor(Color::WHITE),
+        ));
+    });
+}
+
+fn despawn_console(mut commands: Commands, query: Query<Entity, With<DebugConsoleRoot>>) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
+
+// =============================================================================
+// UPDATE
+// =============================================================================
+
+/// While the console is open typing shouldn't move the player, open menus or shoot
+pub fn block_game_input(
+    console: Res<DebugConsole>,
+    mut keys: ResMut<ButtonInput<KeyCode>>,
+    mut mouse: ResMut<ButtonInput<MouseButton>>,
+) {
+    if console.open {
+        keys.reset_all();
+        mouse.reset_all();
+    }
+}
+
+pub fn console_text_input(
+    mut commands: Commands,
+    mut keyboard: MessageReader<KeyboardInput>,
+    mut console: ResMut<DebugConsole>,
+    mut console_commands: MessageWriter<ConsoleCommand>,
+    weapons: Res<WeaponDefinitions>,
+) {
+    if !console.open {
+        keyboard.clear();
+        return;
+    }
+
+    for event in keyboard.read() {
+        if event.state != ButtonState::Pressed || event.key_code == CONSOLE_KEY {
+            continue;
+        }
+
+        match &event.logical_key {
+            Key::Enter => {
+                let line = std::mem::take(&mut console.input);
+                if line.trim().is_empty() {
+                    continue;
+                }
+                info!("Console: {}", line);
+                console.print(format!("> {}", line));
+                match ConsoleCommand::parse(&line, &weapons) {
+                    Ok(command) => {
+                        console_commands.write(command);
+                    }
+                    Err(error) => console.print(error),
+                }
+            }
+            Key::Backspace => {
+                console.input.pop();
+            }
+            Key::Escape => {
+                console.open = false;
+                commands.run_system_cached(despawn_console);
+            }
+            Key::Space => console.input.push(' '),
+            Key::Character(text) => console.input.push_str(text),
+            _ => {}
+        }
+    }
+}
+
+pub fn run_console_commands(
+    mut commands: Commands,
+    mut console_commands: MessageReader<ConsoleCommand>,
+    mut console: ResMut<DebugConsole>,
+    mut player: Query<(Entity, &Transform, &mut Inventory, &mut Health), With<Player>>,
+    difficulty: Res<Difficulty>,
+    weapons: Res<WeaponDefinitions>,
+) {
+    for command in console_commands.read() {
+        match *command {
+            ConsoleCommand::Help => {
+                for line in HELP {
+                    console.print(*line);
+                }
+                continue;
+            }
+            ConsoleCommand::Clear => {
+                console.log.clear();
+                continue;
+            }
+            ConsoleCommand::SkipWave => {
+                commands.run_system_cached(skip_wave);
+                continue;
+            }
+            _ => {}
+        }
+
+        let Ok((player_entity, player_transform, mut inventory, mut health)) = player.single_mut() else {
+            console.print("no player, start a run first");
+            continue;
+        };
+
+        match *command {
+            ConsoleCommand::Spawn { kind, count } => {
+                let config = DroneConfig::from_kind(kind);
+                for i in 0..count {
+                    let pos = ring_position(player_transform.translation.truncate(), i, count, SPAWN_DISTANCE);
+                    spawn_drone(&mut commands, &config, &difficulty, pos, player_entity);
+                }
+                console.print(format!("spawned {} {}", count, kind.name()));
+            }
+            ConsoleCommand::Give { item, count } => {
+                if give_item(&mut inventory, &weapons, item, count) {
+                    console.print(format!("gave {} {}", count, item.name(&weapons)));
+                } else {
+                    console.print(format!("no room for {} (already have it or slots full)", item.name(&weapons)));
+                }
+            }
+            ConsoleCommand::SetHealth(amount) => {
+                health.max = health.max.max(amount);
+                health.current = amount;
+                console.print(format!("health {}/{}", health.current, health.max));
+            }
+            ConsoleCommand::Help | ConsoleCommand::Clear | ConsoleCommand::SkipWave => {}
+        }
+    }
+}
+
+/// Empties the current wave and moves it to InProgress, check_wave_clear ends it as skipped
+fn skip_wave(
+    mut commands: Commands,
+    mut console: ResMut<DebugConsole>,
+    mut wave_state: ResMut<WaveState>,
+    phase: Option<Res<State<WavePhase>>>,
+    mut next_phase: ResMut<NextState<WavePhase>>,
+    enemies: Query<Entity, With<Enemy>>,
+) {
+    let Some(phase) = phase else {
+        console.print("no wave to skip");
+        return;
+    };
+    if *phase.get() == WavePhase::Complete {
+        console.print("no wave to skip");
+        return;
+    }
+
+    for enemy in &enemies {
+        commands.entity(enemy).despawn();
+    }
+    wave_state.reinforcements.clear();
+    wave_state.skipped = true;
+    next_phase.set(WavePhase::InProgress);
+    console.print(format!("skipping wave {}", wave_state.wave_number + 1));
+}
+
+pub fn update_console_text(
+    console: Res<DebugConsole>,
+    mut text: Query<&mut Text, With<DebugConsoleText>>,
+) {
+    let Ok(mut text) = text.single_mut() else { return };
+    if !console.is_changed() && !text.0.is_empty() {
+        return;
+    }
+
+    let mut lines = console.log.clone();
+    lines.push(format!("> {}_", console.input));
+    **text = lines.join("\n");
+}
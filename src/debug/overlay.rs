@@ -0,0 +1,211 @@
+use bevy::diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin};
+use bevy::ecs::entity::Entities;
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use crate::building::Structure;
+use crate::combat::{PlayerOwned, Projectile};
+use crate::game_fonts::GameFonts;
+use crate::npc_behaviors::{CollideTarget, MaintainRangeFromTarget, ShootAtTarget};
+use crate::physics::DesiredDirection;
+use crate::resources::ResourceDrop;
+use crate::spawning::{DroneKind, DroneType, WaveDefinitions, WaveState};
+use crate::state::WavePhase;
+use crate::time_scale::TimeScale;
+
+const OVERLAY_KEY: KeyCode = KeyCode::F3;
+
+/// Length of the DesiredDirection arrows, in world units
+const DIRECTION_ARROW_LENGTH: f32 = 40.0;
+
+#[derive(Resource, Default)]
+pub struct DebugOverlay {
+    pub visible: bool,
+}
+
+pub fn overlay_visible(overlay: Res<DebugOverlay>) -> bool {
+    overlay.visible
+}
+
+// =============================================================================
+// MARKER COMPONENTS
+// =============================================================================
+
+/// Root container for the overlay text (top left)
+#[derive(Component)]
+pub struct DebugOverlayRoot;
+
+#[derive(Component)]
+pub struct DebugOverlayText;
+
+// =============================================================================
+// SPAWN / DESPAWN
+// =============================================================================
+
+pub fn toggle_debug_overlay(
+    mut commands: Commands,
+    input: Res<ButtonInput<KeyCode>>,
+    mut overlay: ResMut<DebugOverlay>,
+) {
+    if !input.just_pressed(OVERLAY_KEY) {
+        return;
+    }
+
+    overlay.visible = !overlay.visible;
+    if overlay.visible {
+        commands.run_system_cached(spawn_debug_overlay);
+    } else {
+        commands.run_system_cached(despawn_debug_overlay);
+    }
+}
+
+fn spawn_debug_overlay(mut commands: Commands, fonts: Res<GameFonts>) {
+    commands.spawn((
+        DebugOverlayRoot,
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(10.0),
+            left: Val::Px(10.0),
+            padding: UiRect::all(Val::Px(8.0)),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+        GlobalZIndex(100),
+    )).with_children(|parent| {
+        parent.spawn((
+            DebugOverlayText,
+            Text::new(""),
+            TextFont { font: fonts.mono.clone(), font_size: 14.0, ..default() },
+            TextColor(Color::srgb(0.0, 1.0, 0.0)),
+        ));
+    });
+}
+
+fn despawn_debug_overlay(mut commands: Commands, query: Query<Entity, With<DebugOverlayRoot>>) {
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
+
+// =============================================================================
+// UPDATE
+// =============================================================================
+
+/// Everything the overlay counts
+#[derive(SystemParam)]
+pub struct OverlayCounts<'w, 's> {
+    entities: &'w Entities,
+    drones: Query<'w, 's, &'static DroneType>,
+    projectiles: Query<'w, 's, Has<PlayerOwned>, With<Projectile>>,
+    structures: Query<'w, 's, (), With<Structure>>,
+    pickups: Query<'w, 's, (), With<ResourceDrop>>,
+}
+
+pub fn update_debug_overlay(
+    diagnostics: Res<DiagnosticsStore>,
+    time_scale: Res<TimeScale>,
+    wave_state: Res<WaveState>,
+    wave_defs: Res<WaveDefinitions>,
+    phase: Option<Res<State<WavePhase>>>,
+    counts: OverlayCounts,
+    mut text: Query<&mut Text, With<DebugOverlayText>>,
+) {
+    let OverlayCounts { entities, drones, projectiles, structures, pickups } = counts;
+    let Ok(mut text) = text.single_mut() else { return };
+
+    let fps = diagnostics
+        .get(&FrameTimeDiagnosticsPlugin::FPS)
+        .and_then(|fps| fps.smoothed())
+        .unwrap_or(0.0);
+
+    let drones_by_kind = DroneKind::ALL
+        .iter()
+        .map(|kind| {
+            let count = drones.iter().filter(|drone| drone.drone_type == *kind).count();
+            format!("{} {}", kind.name(), count)
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let player_projectiles = projectiles.iter().filter(|player_owned| *player_owned).count();
+    let enemy_projectiles = projectiles.iter().count() - player_projectiles;
+
+    let mut lines = vec![
+        format!("FPS {:.0}  time x{:.2}", fps, time_scale.current()),
+        format!("entities {}", entities.len()),
+        format!("drones {} ({})", drones.iter().count(), drones_by_kind),
+        format!("projectiles {} player / {} enemy", player_projectiles, enemy_projectiles),
+        format!("structures {}  pickups {}", structures.iter().count(), pickups.iter().count()),
+    ];
+
+    match phase {
+        Some(phase) => {
+            let wave_size = wave_defs.waves.get(wave_state.wave_number).map_or(0, |wave| wave.drones.len());
+            lines.push(format!(
+                "wave {}/{} {:?}  spawned {}/{}",
+                wave_state.wave_number + 1,
+                wave_defs.waves.len(),
+                phase.get(),
+                wave_state.spawn_index,
+                wave_size,
+            ));
+            let pressure = wave_state.pressure_timer
+                .as_ref()
+                .map_or("-".to_string(), |timer| format!("{:.1}s", timer.remaining_secs()));
+            lines.push(format!(
+                "clock {:.1}s  pressure {}  reinforcements {}",
+                wave_state.wave_clock.elapsed_secs(),
+                pressure,
+                wave_state.reinforcements.len(),
+            ));
+        }
+        None => lines.push("wave -".to_string()),
+    }
+
+    **text = lines.join("\n");
+}
+
+/// Where each AI wants to go this step
+pub fn draw_desired_directions(
+    mut gizmos: Gizmos,
+    query: Query<(&Transform, &DesiredDirection)>,
+) {
+    for (transform, desired) in &query {
+        if desired.0 == Vec2::ZERO {
+            continue;
+        }
+        let pos = transform.translation.truncate();
+        gizmos.arrow_2d(pos, pos + desired.0 * DIRECTION_ARROW_LENGTH, Color::srgb(1.0, 1.0, 0.0));
+    }
+}
+
+/// Line from each AI to whatever its behaviors are targeting, colored by behavior
+pub fn draw_target_lines(
+    mut gizmos: Gizmos,
+    targets: Query<&Transform>,
+    colliders: Query<(&Transform, &CollideTarget)>,
+    keep_away: Query<(&Transform, &MaintainRangeFromTarget)>,
+    shooters: Query<(&Transform, &ShootAtTarget)>,
+) {
+    let mut line_to = |from: &Transform, target: Entity, color: Color| {
+        if let Ok(target_transform) = targets.get(target) {
+            gizmos.line_2d(from.translation.truncate(), target_transform.translation.truncate(), color);
+        }
+    };
+
+    for (transform, collide) in &colliders {
+        line_to(transform, collide.target, Color::srgb(1.0, 0.0, 0.0));
+    }
+    for (transform, maintain) in &keep_away {
+        line_to(transform, maintain.target, Color::srgb(0.0, 1.0, 1.0));
+    }
+    for (transform, shoot) in &shooters {
+        line_to(transform, shoot.target, Color::srgb(1.0, 0.5, 0.0));
+    }
+
+    // keep-away distance around each target
+    for (_, maintain) in &keep_away {
+        if let Ok(target_transform) = targets.get(maintain.target) {
+            gizmos.circle_2d(target_transform.translation.truncate(), maintain.range, Color::srgba(0.0, 1.0, 1.0, 0.2));
+        }
+    }
+}
@@ -0,0 +1,188 @@
+use bevy::prelude::*;
+
+/// Difficulty picked on the run setup screen
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum DifficultyPreset {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+    /// Multipliers set by hand instead of from a preset
+    Custom,
+}
+
+impl DifficultyPreset {
+    /// Presets selectable on the run setup screen (Custom is only built in code)
+    pub const ALL: [DifficultyPreset; 3] = [
+        DifficultyPreset::Easy,
+        DifficultyPreset::Normal,
+        DifficultyPreset::Hard,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            DifficultyPreset::Easy => "Easy",
+            DifficultyPreset::Normal => "Normal",
+            DifficultyPreset::Hard => "Hard",
+            DifficultyPreset::Custom => "Custom",
+        }
+    }
+
+    /// Inverse of `name`, Custom included
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter()
+            .chain([DifficultyPreset::Custom])
+            .find(|preset| preset.name() == name)
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            DifficultyPreset::Easy => Color::srgb(0.0, 1.0, 0.0),
+            DifficultyPreset::Normal => Color::WHITE,
+            DifficultyPreset::Hard => Color::srgb(1.0, 0.3, 0.3),
+            DifficultyPreset::Custom => Color::srgb(0.8, 0.5, 1.0),
+        }
+    }
+}
+
+/// One of the tunable multipliers on Difficulty
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DifficultyMultiplier {
+    DroneHealth,
+    DroneSpeed,
+    DropAmount,
+    PlayerHealth,
+    BeaconChargeTime,
+}
+
+impl DifficultyMultiplier {
+    pub const ALL: [DifficultyMultiplier; 5] = [
+        DifficultyMultiplier::DroneHealth,
+        DifficultyMultiplier::DroneSpeed,
+        DifficultyMultiplier::DropAmount,
+        DifficultyMultiplier::PlayerHealth,
+        DifficultyMultiplier::BeaconChargeTime,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DifficultyMultiplier::DroneHealth => "Drone HP",
+            DifficultyMultiplier::DroneSpeed => "Drone Speed",
+            DifficultyMultiplier::DropAmount => "Drops",
+            DifficultyMultiplier::PlayerHealth => "Player HP",
+            DifficultyMultiplier::BeaconChargeTime => "Beacon Time",
+        }
+    }
+}
+
+const MULTIPLIER_MIN: f32 = 0.25;
+const MULTIPLIER_MAX: f32 = 3.0;
+
+/// Multipliers for the current run, applied when things are spawned
+/// (drones, the player, structures) and when drops are rolled.
+/// 1.0 everywhere is Normal.
+#[derive(Resource, Clone, Copy, PartialEq, Debug)]
+pub struct Difficulty {
+    pub preset: DifficultyPreset,
+    pub drone_health: f32,
+    pub drone_speed: f32,
+    /// Scales DropTable min/max
+    pub drop_amount: f32,
+    pub player_health: f32,
+    /// Scales the extraction beacon's charge time (higher = longer to hold out)
+    pub beacon_charge_time: f32,
+    /// Slow health regeneration out of combat (off on Hard)
+    pub player_regen: bool,
+}
+
+impl Default for Difficulty {
+    fn default() -> Self {
+        Self::from_preset(DifficultyPreset::Normal)
+    }
+}
+
+impl Difficulty {
+    pub fn from_preset(preset: DifficultyPreset) -> Self {
+        match preset {
+            DifficultyPreset::Easy => Self {
+                preset,
+                drone_health: 0.7,
+                drone_speed: 0.85,
+                drop_amount: 1.5,
+                player_health: 1.5,
+                beacon_charge_time: 0.75,
+                player_regen: true,
+            },
+            DifficultyPreset::Normal | DifficultyPreset::Custom => Self {
+                preset,
+                drone_health: 1.0,
+                drone_speed: 1.0,
+                drop_amount: 1.0,
+                player_health: 1.0,
+                beacon_charge_time: 1.0,
+                player_regen: true,
+            },
+            DifficultyPreset::Hard => Self {
+                preset,
+                drone_health: 1.5,
+                drone_speed: 1.2,
+                drop_amount: 0.75,
+                player_health: 0.7,
+                beacon_charge_time: 1.25,
+                player_regen: false,
+            },
+        }
+    }
+
+    pub fn multiplier(&self, multiplier: DifficultyMultiplier) -> f32 {
+        match multiplier {
+            DifficultyMultiplier::DroneHealth => self.drone_health,
+            DifficultyMultiplier::DroneSpeed => self.drone_speed,
+            DifficultyMultiplier::DropAmount => self.drop_amount,
+            DifficultyMultiplier::PlayerHealth => self.player_health,
+            DifficultyMultiplier::BeaconChargeTime => self.beacon_charge_time,
+        }
+    }
+
+    /// Nudges one multiplier by `delta`. Any hand tuning turns the difficulty into Custom
+    pub fn adjust(&mut self, multiplier: DifficultyMultiplier, delta: f32) {
+        let value = match multiplier {
+            DifficultyMultiplier::DroneHealth => &mut self.drone_health,
+            DifficultyMultiplier::DroneSpeed => &mut self.drone_speed,
+            DifficultyMultiplier::DropAmount => &mut self.drop_amount,
+            DifficultyMultiplier::PlayerHealth => &mut self.player_health,
+            DifficultyMultiplier::BeaconChargeTime => &mut self.beacon_charge_time,
+        };
+        *value = (*value + delta).clamp(MULTIPLIER_MIN, MULTIPLIER_MAX);
+        self.preset = DifficultyPreset::Custom;
+    }
+
+    /// Scales the player's base health, never dropping below 1
+    pub fn scale_player_health(&self, base: u32) -> u32 {
+        scale_health(base, self.player_health)
+    }
+
+    /// Scales a drone's base health, never dropping below 1
+    pub fn scale_drone_health(&self, base: u32) -> u32 {
+        scale_health(base, self.drone_health)
+    }
+
+    /// Score multiplier for the run history. Averages how much harder each multiplier
+    /// makes the run than Normal (fewer drops / less player health count as harder)
+    pub fn score_multiplier(&self) -> f32 {
+        (self.drone_health
+            + self.drone_speed
+            + 1.0 / self.drop_amount
+            + 1.0 / self.player_health
+            + self.beacon_charge_time) / 5.0
+    }
+
+    /// Scales a drop count (can round down to 0)
+    pub fn scale_drop(&self, count: u32) -> u32 {
+        (count as f32 * self.drop_amount).round() as u32
+    }
+}
+
+fn scale_health(base: u32, multiplier: f32) -> u32 {
+    ((base as f32 * multiplier).round() as u32).max(1)
+}
@@ -0,0 +1,24 @@
+mod wave_hud;
+mod beacon_hud;
+mod ability_hud;
+
+use bevy::prelude::*;
+use crate::state::GameState;
+
+/// In-game heads-up display (wave progress, beacon charge, ability cooldowns, etc). Spawned with the other
+/// Playing UI so it disappears under the pause/crafting menus.
+pub struct HudPlugin;
+
+impl Plugin for HudPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(OnEnter(GameState::Playing), (wave_hud::spawn_wave_hud, beacon_hud::spawn_beacon_hud, ability_hud::spawn_ability_hud))
+            .add_systems(OnExit(GameState::Playing), (wave_hud::despawn_wave_hud, beacon_hud::despawn_beacon_hud, ability_hud::despawn_ability_hud))
+            .add_systems(Update, (
+                wave_hud::update_wave_hud,
+                wave_hud::show_wave_banner,
+                wave_hud::fade_wave_banner,
+            ).chain().run_if(in_state(GameState::Playing)))
+            .add_systems(Update, (beacon_hud::update_beacon_hud, ability_hud::update_ability_hud).run_if(in_state(GameState::Playing)));
+    }
+}
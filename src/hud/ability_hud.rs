@@ -0,0 +1,110 @@
+use bevy::prelude::*;
+
+use crate::game_fonts::GameFonts;
+use crate::player::Player;
+use crate::player::abilities::{Abilities, Ability};
+
+const SLOT_WIDTH: f32 = 110.0;
+
+// =============================================================================
+// MARKER COMPONENTS
+// =============================================================================
+
+/// Root container for the ability cooldowns, bottom left
+#[derive(Component)]
+pub struct AbilityHud;
+
+/// "[F] Melee" / "Melee 0.4s"
+#[derive(Component)]
+pub struct AbilityHudText(pub Ability);
+
+/// Bar under the text, empties when used and fills back up over the cooldown
+#[derive(Component)]
+pub struct AbilityHudFill(pub Ability);
+
+// =============================================================================
+// SPAWN / DESPAWN
+// =============================================================================
+
+pub fn spawn_ability_hud(mut commands: Commands, fonts: Res<GameFonts>) {
+    commands.spawn((
+        AbilityHud,
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(20.0),
+            left: Val::Px(20.0),
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(6.0),
+            ..default()
+        },
+    )).with_children(|parent| {
+        for ability in Ability::ALL {
+            parent.spawn(Node {
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(2.0),
+                ..default()
+            }).with_children(|slot| {
+                slot.spawn((
+                    AbilityHudText(ability),
+                    Text::new(""),
+                    TextFont { font: fonts.mono.clone(), font_size: 14.0, ..default() },
+                    TextColor(Color::WHITE),
+                ));
+
+                // Bar background
+                slot.spawn((
+                    Node {
+                        width: Val::Px(SLOT_WIDTH),
+                        height: Val::Px(4.0),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgba(0.2, 0.2, 0.2, 0.9)),
+                )).with_children(|bar| {
+                    bar.spawn((
+                        AbilityHudFill(ability),
+                        Node {
+                            width: Val::Percent(100.0),
+                            height: Val::Percent(100.0),
+                            ..default()
+                        },
+                        BackgroundColor(Color::srgb(0.3, 0.8, 1.0)),
+                    ));
+                });
+            });
+        }
+    });
+}
+
+pub fn despawn_ability_hud(mut commands: Commands, query: Query<Entity, With<AbilityHud>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+// =============================================================================
+// UPDATE
+// =============================================================================
+
+pub fn update_ability_hud(
+    player: Query<&Abilities, With<Player>>,
+    mut texts: Query<(&mut Text, &mut TextColor, &AbilityHudText)>,
+    mut fills: Query<(&mut Node, &AbilityHudFill)>,
+) {
+    let Ok(abilities) = player.single() else { return };
+
+    for (mut text, mut color, AbilityHudText(ability)) in &mut texts {
+        let remaining = abilities.remaining_secs(*ability);
+        if abilities.is_ready(*ability) {
+            **text = format!("[{}] {}", ability.key_label(), ability.name());
+            *color = TextColor(Color::WHITE);
+        } else {
+            **text = format!("{} {:.1}s", ability.name(), remaining);
+            *color = TextColor(Color::srgb(0.5, 0.5, 0.5));
+        }
+    }
+
+    for (mut node, AbilityHudFill(ability)) in &mut fills {
+        let ready = 1.0 - abilities.remaining_secs(*ability) / ability.cooldown_secs();
+        node.width = Val::Percent(ready * 100.0);
+    }
+}
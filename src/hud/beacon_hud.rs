@@ -0,0 +1,112 @@
+use bevy::prelude::*;
+
+use crate::building::{BeaconStatus, ExtractionBeacon};
+use crate::game_fonts::GameFonts;
+
+const BAR_WIDTH: f32 = 240.0;
+
+// =============================================================================
+// MARKER COMPONENTS
+// =============================================================================
+
+/// Root container for the beacon charge panel, only shown while a beacon exists
+#[derive(Component)]
+pub struct BeaconHud;
+
+/// "BEACON 42%" / "BEACON UNDER FIRE"
+#[derive(Component)]
+pub struct BeaconHudText;
+
+/// Fill node inside the charge bar
+#[derive(Component)]
+pub struct BeaconHudFill;
+
+// =============================================================================
+// SPAWN / DESPAWN
+// =============================================================================
+
+pub fn spawn_beacon_hud(mut commands: Commands, fonts: Res<GameFonts>) {
+    commands.spawn((
+        BeaconHud,
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(20.0),
+            right: Val::Px(20.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::End,
+            row_gap: Val::Px(4.0),
+            display: Display::None,
+            ..default()
+        },
+    )).with_children(|parent| {
+        parent.spawn((
+            BeaconHudText,
+            Text::new(""),
+            TextFont { font: fonts.mono.clone(), font_size: 18.0, ..default() },
+            TextColor(Color::WHITE),
+        ));
+
+        // Bar background
+        parent.spawn((
+            Node {
+                width: Val::Px(BAR_WIDTH),
+                height: Val::Px(10.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.2, 0.2, 0.2, 0.9)),
+        )).with_children(|bar| {
+            bar.spawn((
+                BeaconHudFill,
+                Node {
+                    width: Val::Percent(0.0),
+                    height: Val::Percent(100.0),
+                    ..default()
+                },
+                BackgroundColor(Color::srgb(0.0, 0.8, 1.0)),
+            ));
+        });
+    });
+}
+
+pub fn despawn_beacon_hud(mut commands: Commands, query: Query<Entity, With<BeaconHud>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+// =============================================================================
+// UPDATE
+// =============================================================================
+
+pub fn update_beacon_hud(
+    beacon_query: Query<&ExtractionBeacon>,
+    mut root: Query<&mut Node, (With<BeaconHud>, Without<BeaconHudFill>)>,
+    mut text: Query<(&mut Text, &mut TextColor), With<BeaconHudText>>,
+    mut fill: Query<(&mut Node, &mut BackgroundColor), With<BeaconHudFill>>,
+) {
+    let Ok(mut root_node) = root.single_mut() else { return };
+
+    let Ok(beacon) = beacon_query.single() else {
+        root_node.display = Display::None;
+        return;
+    };
+    root_node.display = Display::Flex;
+
+    let percent = beacon.progress() * 100.0;
+    let (label, color) = match beacon.status {
+        BeaconStatus::Charging => (format!("BEACON CHARGING {:.0}%", percent), Color::srgb(0.0, 0.8, 1.0)),
+        BeaconStatus::UnderFire => (format!("BEACON UNDER FIRE {:.0}%", percent), Color::srgb(1.0, 0.5, 0.0)),
+        BeaconStatus::Damaged => (format!("BEACON DAMAGED {:.0}%", percent), Color::srgb(1.0, 0.2, 0.2)),
+        BeaconStatus::Charged => ("BEACON CHARGED".to_string(), Color::srgb(0.0, 1.0, 0.0)),
+    };
+
+    if let Ok((mut text, mut text_color)) = text.single_mut() {
+        **text = label;
+        *text_color = TextColor(color);
+    }
+
+    if let Ok((mut node, mut bg)) = fill.single_mut() {
+        node.width = Val::Percent(percent);
+        *bg = BackgroundColor(color);
+    }
+}
@@ -0,0 +1,171 @@
+use bevy::prelude::*;
+
+use crate::enemy::Enemy;
+use crate::game_fonts::GameFonts;
+use crate::spawning::{WaveCleared, WaveDefinitions, WaveState};
+use crate::state::WavePhase;
+
+const BANNER_SECS: f32 = 2.5;
+
+// =============================================================================
+// MARKER COMPONENTS
+// =============================================================================
+
+/// Root container for the wave HUD (top center of the screen)
+#[derive(Component)]
+pub struct WaveHud;
+
+/// "WAVE 2/3"
+#[derive(Component)]
+pub struct WaveNumberText;
+
+/// Countdown during WavePhase::Countdown, drones remaining otherwise
+#[derive(Component)]
+pub struct WaveStatusText;
+
+/// Big "WAVE CLEARED" text, hidden until a WaveCleared message comes in
+#[derive(Component)]
+pub struct WaveBanner(pub Timer);
+
+// =============================================================================
+// SPAWN / DESPAWN
+// =============================================================================
+
+pub fn spawn_wave_hud(mut commands: Commands, fonts: Res<GameFonts>) {
+    let font = fonts.mono.clone();
+    commands.spawn((
+        WaveHud,
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(20.0),
+            width: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            row_gap: Val::Px(4.0),
+            ..default()
+        },
+    )).with_children(|parent| {
+        parent.spawn((
+            WaveNumberText,
+            Text::new(""),
+            TextFont { font: font.clone(), font_size: 28.0, ..default() },
+            TextColor(Color::WHITE),
+        ));
+
+        parent.spawn((
+            WaveStatusText,
+            Text::new(""),
+            TextFont { font: font.clone(), font_size: 18.0, ..default() },
+            TextColor(Color::srgb(0.8, 0.8, 0.8)),
+        ));
+
+        let mut banner_timer = Timer::from_seconds(BANNER_SECS, TimerMode::Once);
+        banner_timer.finish();
+        parent.spawn((
+            WaveBanner(banner_timer),
+            Text::new(""),
+            TextFont { font: font.clone(), font_size: 40.0, ..default() },
+            TextColor(Color::srgb(1.0, 1.0, 0.0)),
+            Visibility::Hidden,
+            Node {
+                margin: UiRect::top(Val::Px(60.0)),
+                ..default()
+            },
+        ));
+    });
+
+    info!("Wave HUD spawned");
+}
+
+pub fn despawn_wave_hud(mut commands: Commands, query: Query<Entity, With<WaveHud>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+// =============================================================================
+// UPDATE
+// =============================================================================
+
+/// Syncs wave number, countdown and remaining drones with WaveState
+pub fn update_wave_hud(
+    wave_state: Res<WaveState>,
+    wave_defs: Res<WaveDefinitions>,
+    phase: Option<Res<State<WavePhase>>>,
+    enemies: Query<(), With<Enemy>>,
+    mut number_text: Query<&mut Text, (With<WaveNumberText>, Without<WaveStatusText>)>,
+    mut status_text: Query<&mut Text, (With<WaveStatusText>, Without<WaveNumberText>)>,
+) {
+    let Some(phase) = phase else { return };
+    let total_waves = wave_defs.waves.len();
+    let current_wave = (wave_state.wave_number + 1).min(total_waves);
+
+    if let Ok(mut text) = number_text.single_mut() {
+        **text = format!("WAVE {}/{}", current_wave, total_waves);
+    }
+
+    // Drones still to come this wave: unspawned wave drones + queued reinforcements
+    let unspawned = match phase.get() {
+        WavePhase::Spawning => wave_defs.waves
+            .get(wave_state.wave_number)
+            .map(|wave| wave.drones.len().saturating_sub(wave_state.spawn_index))
+            .unwrap_or(0),
+        _ => 0,
+    };
+    let remaining = enemies.iter().count() + unspawned + wave_state.reinforcements.len();
+
+    let Ok(mut text) = status_text.single_mut() else { return };
+    **text = match phase.get() {
+        WavePhase::Countdown => {
+            let secs_left = wave_state.countdown_timer.remaining_secs();
+            if remaining > 0 {
+                format!("Next wave in {:.1}s  |  Drones remaining: {}", secs_left, remaining)
+            } else {
+                format!("Next wave in {:.1}s", secs_left)
+            }
+        }
+        WavePhase::Spawning | WavePhase::InProgress => {
+            let pressure = wave_state.pressure_timer.as_ref()
+                .filter(|_| current_wave < total_waves)
+                .filter(|_| *phase.get() == WavePhase::InProgress);
+            match pressure {
+                Some(timer) => format!("Drones remaining: {}  |  Next wave in {:.0}s", remaining, timer.remaining_secs().ceil()),
+                None => format!("Drones remaining: {}", remaining),
+            }
+        }
+        WavePhase::Complete => "All waves cleared".to_string(),
+    };
+}
+
+/// Shows the banner when a wave ends
+pub fn show_wave_banner(
+    mut cleared_messages: MessageReader<WaveCleared>,
+    mut banner: Query<(&mut WaveBanner, &mut Text, &mut Visibility)>,
+) {
+    let Ok((mut banner, mut text, mut visibility)) = banner.single_mut() else { return };
+
+    for message in cleared_messages.read() {
+        **text = if message.skipped {
+            format!("WAVE {} SKIPPED", message.wave_number)
+        } else if message.timed_out {
+            format!("WAVE {} OVERRUN", message.wave_number)
+        } else {
+            format!("WAVE {} CLEARED", message.wave_number)
+        };
+        banner.0.reset();
+        *visibility = Visibility::Inherited;
+    }
+}
+
+/// Hides the banner again once its timer runs out
+pub fn fade_wave_banner(
+    time: Res<Time<Real>>, // UI, ignores time scale
+    mut banner: Query<(&mut WaveBanner, &mut Visibility)>,
+) {
+    for (mut banner, mut visibility) in &mut banner {
+        banner.0.tick(time.delta());
+        if banner.0.just_finished() {
+            *visibility = Visibility::Hidden;
+        }
+    }
+}
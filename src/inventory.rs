@@ -3,8 +3,14 @@ mod inventory_component;
 mod inventory_pickup;
 mod hotbar;
 mod weapon_hotbar;
+mod weapon_drop;
+mod consumable;
+mod consumable_hotbar;
 
 pub use weapon_hotbar::*;
+pub use weapon_drop::*;
+pub use consumable::*;
+pub use consumable_hotbar::*;
 pub use inventory_component::*;
 pub use inventory_pickup::*;
 pub use hotbar::*;
\ No newline at end of file
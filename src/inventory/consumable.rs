@@ -0,0 +1,56 @@
+use bevy::prelude::*;
+
+/// Single-use items. Crafted, kept in the inventory and used from the quick-use bar
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum ConsumableType {
+    /// Heals the player
+    Medkit,
+    /// Thrown at the cursor, blows up drones around where it lands
+    Grenade,
+    /// Faster movement for a while
+    SpeedStim,
+    /// More damage for a while
+    DamageStim,
+    /// Sets a turret down where you're standing, no build mode needed
+    TurretKit,
+}
+
+impl ConsumableType {
+    pub const ALL: [ConsumableType; 5] = [
+        ConsumableType::Medkit,
+        ConsumableType::Grenade,
+        ConsumableType::SpeedStim,
+        ConsumableType::DamageStim,
+        ConsumableType::TurretKit,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ConsumableType::Medkit => "Medkit",
+            ConsumableType::Grenade => "Grenade",
+            ConsumableType::SpeedStim => "Speed Stim",
+            ConsumableType::DamageStim => "Damage Stim",
+            ConsumableType::TurretKit => "Turret Kit",
+        }
+    }
+
+    pub fn glyph(&self) -> &'static str {
+        match self {
+            ConsumableType::Medkit => "+",
+            ConsumableType::Grenade => "o",
+            ConsumableType::SpeedStim => ">",
+            ConsumableType::DamageStim => "!",
+            ConsumableType::TurretKit => "T",
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            ConsumableType::Medkit => Color::srgb(1.0, 0.2, 0.3),
+            ConsumableType::Grenade => Color::srgb(0.4, 0.8, 0.3),
+            ConsumableType::SpeedStim => Color::srgb(0.3, 0.8, 1.0),
+            ConsumableType::DamageStim => Color::srgb(1.0, 0.6, 0.0),
+            ConsumableType::TurretKit => Color::srgb(0.9, 0.9, 0.3),
+        }
+    }
+}
@@ -0,0 +1,160 @@
+use bevy::prelude::*;
+
+use crate::game_fonts::GameFonts;
+use crate::inventory::{CONSUMABLE_SLOTS, Inventory, consumable_at_slot, consumable_count};
+use crate::player::Player;
+use crate::player::buffs::{ActiveBuffs, Buff};
+
+// =============================================================================
+// MARKER COMPONENTS
+// =============================================================================
+
+/// Root container for the quick-use bar, bottom right
+#[derive(Component)]
+pub struct ConsumableHotbar;
+
+/// Marks a quick-use slot (keys 4-8)
+#[derive(Component)]
+pub struct ConsumableHotbarSlot;
+
+#[derive(Component)]
+pub struct ConsumableHotbarGlyph(pub usize);
+
+#[derive(Component)]
+pub struct ConsumableHotbarCount(pub usize);
+
+/// Running buffs, above the bar
+#[derive(Component)]
+pub struct ConsumableHotbarStatus;
+
+// =============================================================================
+// SPAWN / DESPAWN
+// =============================================================================
+
+pub fn spawn_consumable_hotbar(mut commands: Commands, fonts: Res<GameFonts>) {
+    let font = fonts.mono.clone();
+    commands.spawn((
+        ConsumableHotbar,
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(100.0),
+            right: Val::Px(20.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::End,
+            row_gap: Val::Px(6.0),
+            ..default()
+        },
+    )).with_children(|parent| {
+        parent.spawn((
+            ConsumableHotbarStatus,
+            Text::new(""),
+            TextFont { font: font.clone(), font_size: 14.0, ..default() },
+            TextColor(Color::WHITE),
+        ));
+
+        parent.spawn(Node {
+            flex_direction: FlexDirection::Row,
+            column_gap: Val::Px(5.0),
+            ..default()
+        }).with_children(|row| {
+            for i in 0..CONSUMABLE_SLOTS {
+                spawn_consumable_hotbar_slot(row, i, font.clone());
+            }
+        });
+    });
+
+    info!("Consumable Hotbar spawned");
+}
+
+fn spawn_consumable_hotbar_slot(parent: &mut ChildSpawnerCommands, slot_index: usize, font: Handle<Font>) {
+    parent.spawn((
+        ConsumableHotbarSlot,
+        Node {
+            width: Val::Px(50.0),
+            height: Val::Px(50.0),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.2, 0.2, 0.2, 0.9)),
+    )).with_children(|slot: &mut ChildSpawnerCommands| {
+        // Key in the top left, slot 0 is key 4
+        slot.spawn((
+            Text::new((slot_index + 4).to_string()),
+            TextFont { font: font.clone(), font_size: 12.0, ..default() },
+            TextColor(Color::srgb(0.6, 0.6, 0.6)),
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(2.0),
+                left: Val::Px(4.0),
+                ..default()
+            },
+        ));
+        slot.spawn((
+            ConsumableHotbarGlyph(slot_index),
+            Text::new(""),
+            TextFont { font: font.clone(), font_size: 24.0, ..default() },
+            TextColor(Color::WHITE),
+        ));
+        slot.spawn((
+            ConsumableHotbarCount(slot_index),
+            Text::new(""),
+            TextFont { font: font.clone(), font_size: 12.0, ..default() },
+            TextColor(Color::WHITE),
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(2.0),
+                right: Val::Px(4.0),
+                ..default()
+            },
+        ));
+    });
+}
+
+pub fn despawn_consumable_hotbar(mut commands: Commands, query: Query<Entity, With<ConsumableHotbar>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+// =============================================================================
+// UPDATE
+// =============================================================================
+
+pub fn update_consumable_hotbar(
+    player_query: Query<&Inventory, With<Player>>,
+    mut glyphs: Query<(&mut Text, &mut TextColor, &ConsumableHotbarGlyph)>,
+    mut counts: Query<(&mut Text, &ConsumableHotbarCount), Without<ConsumableHotbarGlyph>>,
+) {
+    let Ok(inventory) = player_query.single() else { return };
+
+    for (mut text, mut color, glyph) in &mut glyphs {
+        if let Some(consumable) = consumable_at_slot(inventory, glyph.0) {
+            **text = consumable.glyph().into();
+            *color = TextColor(consumable.color());
+        } else {
+            **text = String::new();
+        }
+    }
+
+    for (mut text, count) in &mut counts {
+        **text = consumable_at_slot(inventory, count.0)
+            .map(|consumable| consumable_count(inventory, consumable).to_string())
+            .unwrap_or_default();
+    }
+}
+
+/// "Speed 4.2s" for each running buff
+pub fn update_consumable_hotbar_status(
+    player_query: Query<&ActiveBuffs, With<Player>>,
+    mut status: Single<&mut Text, With<ConsumableHotbarStatus>>,
+) {
+    let Ok(buffs) = player_query.single() else { return };
+
+    let lines: Vec<String> = Buff::ALL.into_iter()
+        .filter(|buff| buffs.is_active(*buff))
+        .map(|buff| format!("{} {:.1}s", buff.name(), buffs.remaining_secs(buff)))
+        .collect();
+
+    ***status = lines.join("\n");
+}
@@ -1,15 +1,24 @@
 use bevy::prelude::*;
 use std::collections::HashMap;
-use crate::{building::PlaceableType, combat::WeaponType, resources::ResourceType};
+use crate::{building::{PlaceableConfig, PlaceableType}, combat::{Weapon, WeaponDefinitions, WeaponId}, resources::ResourceType};
+use super::consumable::ConsumableType;
 
 pub const WEAPON_SLOTS: usize = 3;
+/// One quick-use slot for every kind of consumable, so none are stuck without one
+pub const CONSUMABLE_SLOTS: usize = ConsumableType::ALL.len();
 
 #[derive(Component)]
 pub struct Inventory{
     pub resource_inventory: HashMap<ResourceType, u32>, //stackable
-    pub weapon_slots: [Option<WeaponType>; WEAPON_SLOTS],
+    pub weapon_slots: [Option<Weapon>; WEAPON_SLOTS], //each slot keeps its own cooldown / ammo
     pub active_weapon_slot: usize,
+    /// Some right after switching weapons, can't fire until it finishes
+    pub weapon_swap: Option<Timer>,
     pub placeable_inventory: HashMap<PlaceableType, u32>, //stackable
+    pub consumable_inventory: HashMap<ConsumableType, u32>, //stackable
+    /// Quick-use bar. Consumables go in the first empty slot when picked up and leave it
+    /// when the last one is used
+    pub consumable_slots: [Option<ConsumableType>; CONSUMABLE_SLOTS],
 }
 
 pub fn read_resource_inventory(inventory: &Inventory){
@@ -26,7 +35,7 @@ pub fn read_resource_inventory(inventory: &Inventory){
 pub fn read_weapon_inventory(inventory: &Inventory) {
     for (i, slot) in inventory.weapon_slots.iter().enumerate() {
         match slot {
-            Some(weapon_type) => info!("Slot {}: {}", i + 1, weapon_type.name()),
+            Some(weapon) => info!("Slot {}: {}", i + 1, weapon.weapon_id.as_str()),
             None => info!("Slot {}: Empty", i + 1),
         }
     }
@@ -63,32 +72,102 @@ pub fn has_resources(inventory: &Inventory, requirements: &[(ResourceType, u32)]
     true
 }
 
-/// Add a weapon to the first empty slot. Returns the slot index, or None if full.
-pub fn add_weapon(inventory: &mut Inventory, weapon: WeaponType) -> Option<usize> {
-    // Don't add duplicates
-    if inventory.weapon_slots.iter().any(|s| *s == Some(weapon)) {
-        return None;
+/// Why a weapon can't go into the inventory
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum WeaponSlotError {
+    AlreadyOwned,
+    SlotsFull,
+}
+
+/// Slot a new weapon would go in: the first empty one. Duplicates aren't allowed
+pub fn free_weapon_slot(inventory: &Inventory, weapon: WeaponId) -> Result<usize, WeaponSlotError> {
+    if weapon_slot_of(inventory, weapon).is_some() {
+        return Err(WeaponSlotError::AlreadyOwned);
     }
-    // Find first empty slot
-    for (i, slot) in inventory.weapon_slots.iter_mut().enumerate() {
-        if slot.is_none() {
-            *slot = Some(weapon);
-            return Some(i);
-        }
+    inventory.weapon_slots.iter().position(|slot| slot.is_none()).ok_or(WeaponSlotError::SlotsFull)
+}
+
+/// Puts an existing weapon (e.g. one picked up off the ground) in the first empty slot
+pub fn put_weapon(inventory: &mut Inventory, weapon: Weapon) -> Result<usize, WeaponSlotError> {
+    let i = free_weapon_slot(inventory, weapon.weapon_id)?;
+    inventory.weapon_slots[i] = Some(weapon);
+    Ok(i)
+}
+
+/// Add a weapon to the first empty slot with a full magazine. Returns the slot index,
+/// or None if full, already owned (see free_weapon_slot for which) or the weapon isn't defined.
+pub fn add_weapon(inventory: &mut Inventory, weapons: &WeaponDefinitions, weapon: WeaponId) -> Option<usize> {
+    let definition = weapons.get(weapon)?;
+    put_weapon(inventory, Weapon::from_definition(definition)).ok()
+}
+
+/// Takes the weapon out of `slot`, holstered. If it was in hand the next weapon along is
+/// switched to (with the usual swap delay)
+pub fn take_weapon(inventory: &mut Inventory, slot: usize) -> Option<Weapon> {
+    let mut weapon = inventory.weapon_slots.get_mut(slot)?.take()?;
+    weapon.holster();
+
+    if slot == inventory.active_weapon_slot
+        && let Some(next) = (1..WEAPON_SLOTS).map(|offset| (slot + offset) % WEAPON_SLOTS)
+            .find(|i| inventory.weapon_slots[*i].is_some())
+    {
+        switch_weapon(inventory, next);
     }
-    None
+    Some(weapon)
+}
+
+/// Swaps the weapon in `slot` for `weapon`, returning the old one holstered. Goes through
+/// the swap delay if it's the slot in hand
+pub fn replace_weapon(inventory: &mut Inventory, slot: usize, weapon: Weapon) -> Option<Weapon> {
+    let swap_secs = weapon.swap_secs;
+    let mut old = inventory.weapon_slots.get_mut(slot)?.replace(weapon);
+    if let Some(old) = old.as_mut() {
+        old.holster();
+    }
+
+    if slot == inventory.active_weapon_slot {
+        inventory.weapon_swap = Some(Timer::from_seconds(swap_secs, TimerMode::Once));
+    }
+    old
 }
 
 /// Get the weapon in a specific slot
-pub fn weapon_at_slot(inventory: &Inventory, slot: usize) -> Option<WeaponType> {
-    inventory.weapon_slots.get(slot).copied().flatten()
+pub fn weapon_at_slot(inventory: &Inventory, slot: usize) -> Option<&Weapon> {
+    inventory.weapon_slots.get(slot).and_then(Option::as_ref)
+}
+
+/// Slot holding this weapon, if the player has it
+pub fn weapon_slot_of(inventory: &Inventory, weapon: WeaponId) -> Option<usize> {
+    inventory.weapon_slots.iter().position(|slot| slot.as_ref().is_some_and(|w| w.weapon_id == weapon))
 }
 
 /// Get the currently active weapon
-pub fn active_weapon(inventory: &Inventory) -> Option<WeaponType> {
+pub fn active_weapon(inventory: &Inventory) -> Option<&Weapon> {
     weapon_at_slot(inventory, inventory.active_weapon_slot)
 }
 
+pub fn active_weapon_mut(inventory: &mut Inventory) -> Option<&mut Weapon> {
+    inventory.weapon_slots.get_mut(inventory.active_weapon_slot).and_then(Option::as_mut)
+}
+
+/// Makes `slot` the active weapon. The old one stops reloading / charging and the new one
+/// can't fire until its swap delay is over. Returns false for empty or already active slots
+pub fn switch_weapon(inventory: &mut Inventory, slot: usize) -> bool {
+    if slot == inventory.active_weapon_slot {
+        return false;
+    }
+    let Some(swap_secs) = weapon_at_slot(inventory, slot).map(|weapon| weapon.swap_secs) else {
+        return false;
+    };
+
+    if let Some(current) = active_weapon_mut(inventory) {
+        current.holster();
+    }
+    inventory.active_weapon_slot = slot;
+    inventory.weapon_swap = Some(Timer::from_seconds(swap_secs, TimerMode::Once));
+    true
+}
+
 pub fn add_placeable(inventory: &mut Inventory, placeable: PlaceableType, count: u32) {
     *inventory.placeable_inventory.entry(placeable).or_insert(0) += count;
 }
@@ -117,13 +196,197 @@ pub fn first_available_placeable(inventory: &Inventory) -> Option<PlaceableType>
         .map(|(kind, _)| *kind)
 }
 
-impl Default for Inventory {
-    fn default() -> Self {
-        Self {
+pub fn consumable_count(inventory: &Inventory, consumable: ConsumableType) -> u32 {
+    inventory.consumable_inventory.get(&consumable).copied().unwrap_or(0)
+}
+
+/// Get the consumable in a quick-use slot
+pub fn consumable_at_slot(inventory: &Inventory, slot: usize) -> Option<ConsumableType> {
+    inventory.consumable_slots.get(slot).copied().flatten()
+}
+
+pub fn add_consumable(inventory: &mut Inventory, consumable: ConsumableType, count: u32) {
+    if count == 0 {
+        return;
+    }
+    *inventory.consumable_inventory.entry(consumable).or_insert(0) += count;
+    fill_consumable_slots(inventory);
+}
+
+/// Uses up one. When the last one goes its quick-use slot is freed
+pub fn remove_consumable(inventory: &mut Inventory, consumable: ConsumableType) -> bool {
+    let Some(cur_count) = inventory.consumable_inventory.get_mut(&consumable) else {
+        return false;
+    };
+    *cur_count = cur_count.saturating_sub(1);
+    if *cur_count == 0 {
+        inventory.consumable_inventory.remove(&consumable);
+        for slot in inventory.consumable_slots.iter_mut().filter(|slot| **slot == Some(consumable)) {
+            *slot = None;
+        }
+    }
+    true
+}
+
+/// Puts held consumables without a slot into empty slots, in ConsumableType::ALL order
+fn fill_consumable_slots(inventory: &mut Inventory) {
+    for consumable in ConsumableType::ALL {
+        if consumable_count(inventory, consumable) == 0 || inventory.consumable_slots.contains(&Some(consumable)) {
+            continue;
+        }
+        let Some(slot) = inventory.consumable_slots.iter_mut().find(|slot| slot.is_none()) else {
+            return;
+        };
+        *slot = Some(consumable);
+    }
+}
+
+/// Anything that can be given to the player by name (console `give`, `--give` on the command line)
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum InventoryItem {
+    Resource(ResourceType),
+    Weapon(WeaponId),
+    Placeable(PlaceableType),
+    Consumable(ConsumableType),
+}
+
+/// Lowercase with spaces dropped, so "scrap" finds "Scrap Metal"
+fn normalize_name(name: &str) -> String {
+    name.chars().filter(|c| !c.is_whitespace()).flat_map(char::to_lowercase).collect()
+}
+
+impl InventoryItem {
+    pub fn name<'a>(&self, weapons: &'a WeaponDefinitions) -> &'a str {
+        match self {
+            InventoryItem::Resource(resource) => resource.name(),
+            InventoryItem::Weapon(weapon) => weapons.name(*weapon),
+            InventoryItem::Placeable(placeable) => PlaceableConfig::from_type(placeable).name,
+            InventoryItem::Consumable(consumable) => consumable.name(),
+        }
+    }
+
+    /// First item whose name starts with `typed`, ignoring case and spaces. The turret gun
+    /// only belongs on turrets so it can't be given
+    pub fn from_name(typed: &str, weapons: &WeaponDefinitions) -> Option<Self> {
+        let typed = normalize_name(typed);
+        if typed.is_empty() {
+            return None;
+        }
+
+        ResourceType::ALL.into_iter().map(InventoryItem::Resource)
+            .chain(weapons.iter()
+                .filter(|weapon| weapon.id != WeaponId::TURRET)
+                .map(|weapon| InventoryItem::Weapon(weapon.id)))
+            .chain(PlaceableType::BUILDABLE.into_iter().map(InventoryItem::Placeable))
+            .chain(ConsumableType::ALL.into_iter().map(InventoryItem::Consumable))
+            .find(|item| normalize_name(item.name(weapons)).starts_with(&typed))
+    }
+}
+
+/// Adds `count` of the item (weapons only ever go in once). Returns false if a weapon had no room
+pub fn give_item(inventory: &mut Inventory, weapons: &WeaponDefinitions, item: InventoryItem, count: u32) -> bool {
+    match item {
+        InventoryItem::Resource(resource) => add_resource(inventory, resource, count),
+        InventoryItem::Weapon(weapon) => return add_weapon(inventory, weapons, weapon).is_some(),
+        InventoryItem::Placeable(placeable) => add_placeable(inventory, placeable, count),
+        InventoryItem::Consumable(consumable) => add_consumable(inventory, consumable, count),
+    }
+    true
+}
+
+impl Inventory {
+    /// Starting inventory: just the pistol
+    pub fn new(weapons: &WeaponDefinitions) -> Self {
+        let mut inventory = Self {
             resource_inventory: HashMap::new(),
-            weapon_slots: [Some(WeaponType::Pistol), None, None],
+            weapon_slots: std::array::from_fn(|_| None),
             active_weapon_slot: 0,
+            weapon_swap: None,
             placeable_inventory: HashMap::new(),
+            consumable_inventory: HashMap::new(),
+            consumable_slots: [None; CONSUMABLE_SLOTS],
+        };
+        add_weapon(&mut inventory, weapons, WeaponId::PISTOL);
+        inventory
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inventory() -> Inventory {
+        Inventory::new(&WeaponDefinitions::parse(""))
+    }
+
+    #[test]
+    fn add_consumable_takes_the_first_empty_slot_once() {
+        let mut inventory = inventory();
+        add_consumable(&mut inventory, ConsumableType::Grenade, 2);
+        add_consumable(&mut inventory, ConsumableType::Medkit, 1);
+        add_consumable(&mut inventory, ConsumableType::Grenade, 3);
+
+        assert_eq!(consumable_count(&inventory, ConsumableType::Grenade), 5);
+        assert_eq!(consumable_at_slot(&inventory, 0), Some(ConsumableType::Grenade));
+        assert_eq!(consumable_at_slot(&inventory, 1), Some(ConsumableType::Medkit));
+        assert_eq!(consumable_at_slot(&inventory, 2), None);
+    }
+
+    #[test]
+    fn add_nothing_takes_no_slot() {
+        let mut inventory = inventory();
+        add_consumable(&mut inventory, ConsumableType::Medkit, 0);
+        assert_eq!(consumable_at_slot(&inventory, 0), None);
+        assert!(!inventory.consumable_inventory.contains_key(&ConsumableType::Medkit));
+    }
+
+    #[test]
+    fn every_consumable_gets_a_slot() {
+        let mut inventory = inventory();
+        for consumable in ConsumableType::ALL.into_iter().rev() {
+            add_consumable(&mut inventory, consumable, 1);
+        }
+        for consumable in ConsumableType::ALL {
+            assert!(inventory.consumable_slots.contains(&Some(consumable)), "{} has no slot", consumable.name());
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn remove_consumable_frees_the_slot_with_the_last_one() {
+        let mut inventory = inventory();
+        add_consumable(&mut inventory, ConsumableType::SpeedStim, 2);
+
+        assert!(remove_consumable(&mut inventory, ConsumableType::SpeedStim));
+        assert_eq!(consumable_count(&inventory, ConsumableType::SpeedStim), 1);
+        assert_eq!(consumable_at_slot(&inventory, 0), Some(ConsumableType::SpeedStim));
+
+        assert!(remove_consumable(&mut inventory, ConsumableType::SpeedStim));
+        assert_eq!(consumable_count(&inventory, ConsumableType::SpeedStim), 0);
+        assert_eq!(consumable_at_slot(&inventory, 0), None);
+
+        assert!(!remove_consumable(&mut inventory, ConsumableType::SpeedStim));
+    }
+
+    #[test]
+    fn freed_slot_is_refilled_by_the_next_pickup() {
+        let mut inventory = inventory();
+        add_consumable(&mut inventory, ConsumableType::Medkit, 1);
+        add_consumable(&mut inventory, ConsumableType::Grenade, 1);
+        remove_consumable(&mut inventory, ConsumableType::Medkit);
+
+        add_consumable(&mut inventory, ConsumableType::TurretKit, 1);
+        assert_eq!(consumable_at_slot(&inventory, 0), Some(ConsumableType::TurretKit));
+        assert_eq!(consumable_at_slot(&inventory, 1), Some(ConsumableType::Grenade));
+
+        // coming back later it goes to the end, the others keep their keys
+        add_consumable(&mut inventory, ConsumableType::Medkit, 1);
+        assert_eq!(consumable_at_slot(&inventory, 2), Some(ConsumableType::Medkit));
+    }
+
+    #[test]
+    fn turret_gun_cant_be_given_by_name() {
+        let weapons = WeaponDefinitions::parse(include_str!("../../assets/weapons.txt"));
+        assert_eq!(InventoryItem::from_name("sentry", &weapons), None);
+        assert_eq!(InventoryItem::from_name("turret", &weapons), Some(InventoryItem::Placeable(PlaceableType::Turret)));
+    }
+}
@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 
-use crate::{inventory::Inventory, resources::ResourceDrop, state::GameState};
+use crate::{inventory::Inventory, physics::SimulationSet, resources::ResourceDrop, run_stats::RunStats, state::GameState};
 use super::inventory_component::add_resource;
 
 const PICKUP_DISTANCE: f32 = 20.0;
@@ -42,12 +42,14 @@ fn handle_pickup(
     mut inventories: Query<&mut Inventory>,
     mut pickup_messages: MessageReader<PickupEvent>, 
     resources: Query<&ResourceDrop>,
+    mut stats: ResMut<RunStats>,
 ) {
     for message in pickup_messages.read() {
         if let Ok(mut inventory) = inventories.get_mut(message.collector)
             && let Ok(resource) = resources.get(message.resource_entity)
         {
             add_resource(&mut inventory, resource.resource_type, 1);
+            stats.record_pickup(resource.resource_type, 1);
             commands.entity(message.resource_entity).try_despawn();
             //spawn it into hotbar!
         }
@@ -59,6 +61,9 @@ pub struct PickupPlugin;
 impl Plugin for PickupPlugin {
     fn build(&self, app: &mut App) {
         app.add_message::<PickupEvent>()
-            .add_systems(Update, (detect_resource_pickup, handle_pickup).chain().run_if(in_state(GameState::Playing)));
+            .add_systems(FixedUpdate, (detect_resource_pickup, handle_pickup)
+                .chain()
+                .in_set(SimulationSet::Collision)
+                .run_if(in_state(GameState::Playing)));
     }
 }
\ No newline at end of file
@@ -0,0 +1,108 @@
+use bevy::prelude::*;
+
+use crate::ascii_sprite::AsciiSprite;
+use crate::combat::{Weapon, WeaponDefinitions};
+use crate::player::Player;
+use super::inventory_component::{Inventory, WeaponSlotError, active_weapon, free_weapon_slot};
+
+/// How close the player has to be to pick a weapon up
+pub const WEAPON_PICKUP_RANGE: f32 = 40.0;
+
+/// A weapon lying on the ground. Keeps the whole instance so ammo and upgrades
+/// come back with it
+#[derive(Component)]
+pub struct WeaponPickup(pub Weapon);
+
+/// "[E] Pick up ..." text shown while standing next to a WeaponPickup
+#[derive(Component)]
+pub struct WeaponPickupPrompt;
+
+pub fn spawn_weapon_pickup(commands: &mut Commands, weapons: &WeaponDefinitions, weapon: Weapon, position: Vec2) {
+    let Some(definition) = weapons.get(weapon.weapon_id) else { return };
+
+    commands.spawn((
+        Transform::from_translation(position.extend(0.0)),
+        Visibility::Inherited,
+        AsciiSprite {
+            glyph: definition.glyph.clone(),
+            color: definition.color,
+            font_size: 24.0,
+            bg_color: None,
+        },
+        WeaponPickup(weapon),
+    ));
+}
+
+/// Closest pickup within WEAPON_PICKUP_RANGE of `position`
+pub fn nearest_weapon_pickup<'a>(
+    position: Vec2,
+    pickups: impl Iterator<Item = (Entity, &'a Transform, &'a WeaponPickup)>,
+) -> Option<(Entity, Vec2, &'a WeaponPickup)> {
+    pickups
+        .map(|(entity, transform, pickup)| (entity, transform.translation.truncate(), pickup))
+        .filter(|(_, pickup_position, _)| pickup_position.distance(position) <= WEAPON_PICKUP_RANGE)
+        .min_by(|(_, a, _), (_, b, _)| a.distance(position).total_cmp(&b.distance(position)))
+}
+
+// =============================================================================
+// SPAWN / DESPAWN
+// =============================================================================
+
+pub fn spawn_weapon_pickup_prompt(mut commands: Commands) {
+    commands.spawn((
+        WeaponPickupPrompt,
+        Text::new(""),
+        TextFont {
+            font_size: 18.0,
+            ..default()
+        },
+        TextColor(Color::WHITE),
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(190.0),
+            width: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        TextLayout::new_with_justify(Justify::Center),
+    ));
+}
+
+pub fn despawn_weapon_pickup_prompt(mut commands: Commands, query: Query<Entity, With<WeaponPickupPrompt>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+// =============================================================================
+// UPDATE
+// =============================================================================
+
+/// What pressing E next to the nearest weapon would do. With every slot full it replaces
+/// the weapon in hand
+pub fn update_weapon_pickup_prompt(
+    player: Single<(&Transform, &Inventory), With<Player>>,
+    pickups: Query<(Entity, &Transform, &WeaponPickup)>,
+    mut prompt: Single<&mut Text, With<WeaponPickupPrompt>>,
+    weapons: Res<WeaponDefinitions>,
+) {
+    let (transform, inventory) = player.into_inner();
+    let Some((_, _, pickup)) = nearest_weapon_pickup(transform.translation.truncate(), pickups.iter()) else {
+        prompt.0.clear();
+        return;
+    };
+
+    let mut name = weapons.name(pickup.0.weapon_id).to_string();
+    if !pickup.0.modifiers.is_empty() {
+        name = format!("{} (+{} upgrades)", name, pickup.0.modifiers.len());
+    }
+
+    prompt.0 = match free_weapon_slot(inventory, pickup.0.weapon_id) {
+        Ok(_) => format!("[E] Pick up {}", name),
+        Err(WeaponSlotError::AlreadyOwned) => format!("Already carrying a {}", weapons.name(pickup.0.weapon_id)),
+        Err(WeaponSlotError::SlotsFull) => {
+            let held = active_weapon(inventory).map_or("", |weapon| weapons.name(weapon.weapon_id));
+            format!("Weapon slots full\n[E] Swap {} for {}", held, name)
+        }
+    };
+}
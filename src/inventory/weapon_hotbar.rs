@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 
-use crate::{inventory::{Inventory, WEAPON_SLOTS}, player::Player};
+use crate::{combat::{WeaponDefinitions, MAX_MODIFIERS}, inventory::{Inventory, WEAPON_SLOTS, weapon_at_slot}, player::Player};
 
 /// Root container for the weapon hotbar UI
 #[derive(Component)]
@@ -14,6 +14,14 @@ pub struct WeaponHotbarSlot(pub usize);
 #[derive(Component)]
 pub struct WeaponHotbarGlyph(pub usize);
 
+/// Magazine / reserve count under a slot
+#[derive(Component)]
+pub struct WeaponHotbarAmmo(pub usize);
+
+/// Name and upgrades of the hovered slot's weapon, shown above the hotbar
+#[derive(Component)]
+pub struct WeaponHotbarTooltip;
+
 pub fn despawn_weapon_hotbar(mut commands: Commands, query: Query<Entity, With<WeaponHotbar>>) {
     for entity in query.iter() {
         commands.entity(entity).despawn();
@@ -36,6 +44,25 @@ pub fn spawn_weapon_hotbar(mut commands: Commands){
         for i in 0..WEAPON_SLOTS {
             spawn_weapon_hotbar_slot(parent, i);
         }
+
+        // hidden until a slot is hovered
+        parent.spawn((
+            WeaponHotbarTooltip,
+            Text::new(""),
+            TextFont {
+                font_size: 14.0,
+                ..default()
+            },
+            TextColor(Color::WHITE),
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(58.0),
+                padding: UiRect::all(Val::Px(6.0)),
+                display: Display::None,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.85)),
+        ));
     });
 
     info!("Weapon Hotbar spawned");
@@ -52,6 +79,8 @@ fn spawn_weapon_hotbar_slot(parent: &mut ChildSpawnerCommands, slot_index: usize
             ..default()
         },
         BackgroundColor(Color::srgba(0.2, 0.2, 0.2, 0.9)),
+        // for the hover tooltip
+        Interaction::default(),
     )).with_children(|slot: &mut ChildSpawnerCommands| {
         // Glyph in the center (blank, you populate it)
         slot.spawn((
@@ -63,6 +92,21 @@ fn spawn_weapon_hotbar_slot(parent: &mut ChildSpawnerCommands, slot_index: usize
             },
             TextColor(Color::WHITE),
         ));
+        // Ammo count hanging under the slot
+        slot.spawn((
+            WeaponHotbarAmmo(slot_index),
+            Text::new(""),
+            TextFont {
+                font_size: 12.0,
+                ..default()
+            },
+            TextColor(Color::srgb(0.85, 0.85, 0.85)),
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(-16.0),
+                ..default()
+            },
+        ));
     });
 }
 
@@ -70,14 +114,15 @@ pub fn update_weapon_hotbar(
     player_query: Query<&Inventory, With<Player>>,
     mut glyphs: Query<(&mut Text, &mut TextColor, &WeaponHotbarGlyph)>,
     mut slots: Query<(&mut BackgroundColor, &WeaponHotbarSlot)>,
+    weapons: Res<WeaponDefinitions>,
 ) {
     let Ok(inventory) = player_query.single() else { return };
 
     // Update glyphs from weapon slots
     for (mut text, mut color, glyph) in &mut glyphs {
-        if let Some(Some(weapon_type)) = inventory.weapon_slots.get(glyph.0) {
-            **text = weapon_type.glyph().into();
-            *color = TextColor(weapon_type.color());
+        if let Some(definition) = weapon_at_slot(inventory, glyph.0).and_then(|weapon| weapons.get(weapon.weapon_id)) {
+            **text = definition.glyph.clone();
+            *color = TextColor(definition.color);
         } else {
             **text = String::new();
         }
@@ -91,4 +136,67 @@ pub fn update_weapon_hotbar(
             *bg = BackgroundColor(Color::srgba(0.2, 0.2, 0.2, 0.9));
         }
     }
-}
\ No newline at end of file
+}
+
+/// "magazine/reserve" under each weapon (no reserve for unlimited ammo), "reloading" while
+/// it reloads
+pub fn update_weapon_hotbar_ammo(
+    player_query: Query<&Inventory, With<Player>>,
+    mut ammo_texts: Query<(&mut Text, &mut TextColor, &WeaponHotbarAmmo)>,
+) {
+    let Ok(inventory) = player_query.single() else { return };
+
+    for (mut text, mut color, slot) in &mut ammo_texts {
+        let Some(weapon) = weapon_at_slot(inventory, slot.0) else {
+            **text = String::new();
+            continue;
+        };
+
+        let ammo = weapon.ammo;
+        **text = if weapon.reload.is_some() {
+            "reloading".to_string()
+        } else {
+            match ammo.reserve {
+                Some(reserve) => format!("{}/{}", ammo.magazine, reserve),
+                None => ammo.magazine.to_string(),
+            }
+        };
+        // red when the weapon is completely dry
+        *color = if ammo.magazine == 0 && ammo.reserve == Some(0) {
+            TextColor(Color::srgb(1.0, 0.3, 0.3))
+        } else {
+            TextColor(Color::srgb(0.85, 0.85, 0.85))
+        };
+    }
+}
+
+/// Shows the hovered weapon's name and its upgrades
+pub fn update_weapon_hotbar_tooltip(
+    player_query: Query<&Inventory, With<Player>>,
+    slots: Query<(&Interaction, &WeaponHotbarSlot)>,
+    tooltip: Single<(&mut Text, &mut Node), With<WeaponHotbarTooltip>>,
+    weapons: Res<WeaponDefinitions>,
+) {
+    let Ok(inventory) = player_query.single() else { return };
+    let (mut text, mut node) = tooltip.into_inner();
+
+    let hovered = slots.iter()
+        .find(|(interaction, _)| **interaction != Interaction::None)
+        .and_then(|(_, slot)| weapon_at_slot(inventory, slot.0));
+
+    let Some(weapon) = hovered else {
+        node.display = Display::None;
+        return;
+    };
+
+    let mut lines = vec![weapons.name(weapon.weapon_id).to_string()];
+    if weapon.modifiers.is_empty() {
+        lines.push("No upgrades".to_string());
+    } else {
+        lines.extend(weapon.modifiers.iter().map(|modifier| format!("- {}", modifier.name())));
+    }
+    lines.push(format!("Upgrade slots: {}/{}", weapon.modifiers.len(), MAX_MODIFIERS));
+
+    **text = lines.join("\n");
+    node.display = Display::Flex;
+}
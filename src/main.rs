@@ -5,6 +5,7 @@ mod combat;
 mod enemy;
 mod game_fonts;
 mod helpers;
+mod hud;
 mod npc_behaviors;
 mod physics;
 mod player;
@@ -14,22 +15,47 @@ mod state;
 mod inventory;
 mod audio;
 mod crafting;
+mod difficulty;
+mod cli;
+mod game_rng;
+mod run_stats;
+mod run_history;
+mod replay;
+mod time_scale;
+mod debug;
 mod building;
 
 use camera::CameraPlugin;
+use hud::HudPlugin;
 use audio::settings::AudioSettings;
-use combat::CombatPlugin;
+use combat::{CombatPlugin, WeaponDefinitions};
 use inventory::PickupPlugin;
 use physics::PhysicsPlugin;
 use player::PlayerPlugin;
 use ascii_sprite::render_ascii_sprites;
 use resources::{DropTable, ResourcePlugin};
+use difficulty::Difficulty;
+use cli::CliArgs;
+use game_rng::GameRng;
+use run_stats::RunStatsPlugin;
+use run_history::RunHistoryPlugin;
+use replay::ReplayPlugin;
+use time_scale::TimeScalePlugin;
+use debug::DebugPlugin;
 
 use crate::npc_behaviors::NpcBehaviorPlugins;
 
 fn main() {
-    App::new()
-        .add_plugins(DefaultPlugins)
+    let mut app = App::new();
+    app.add_plugins(DefaultPlugins);
+
+    // loaded after DefaultPlugins so bad weapons / arguments get logged
+    let weapons = WeaponDefinitions::load();
+    let cli = CliArgs::parse(&weapons);
+
+    app
+        .insert_resource(weapons)
+        .insert_resource(cli)
         .add_plugins(state::StatePlugin)
         .add_plugins(PhysicsPlugin)
         .add_plugins(CombatPlugin)
@@ -39,7 +65,15 @@ fn main() {
         .add_plugins(ResourcePlugin)
         .add_plugins(PickupPlugin)
         .add_plugins(building::BuildingPlugin)
+        .add_plugins(HudPlugin)
+        .add_plugins(RunStatsPlugin)
+        .add_plugins(RunHistoryPlugin)
+        .add_plugins(ReplayPlugin)
+        .add_plugins(TimeScalePlugin)
+        .add_plugins(DebugPlugin)
         .init_resource::<DropTable>()
+        .init_resource::<Difficulty>()
+        .init_resource::<GameRng>()
         .init_resource::<AudioSettings>()
         .add_systems(Update, render_ascii_sprites)
         .run();
@@ -1,5 +1,5 @@
 use bevy::prelude::*;
-use crate::{physics::DesiredDirection, state::GameState};
+use crate::{physics::{DesiredDirection, SimulationSet}, state::GameState};
 
 #[derive(Component)]
   pub struct CollideTarget {
@@ -26,6 +26,8 @@ pub struct CollideTargetPlugin;
 
 impl Plugin for CollideTargetPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, collide_target_system.run_if(in_state(GameState::Playing)));
+        app.add_systems(FixedUpdate, collide_target_system
+            .in_set(SimulationSet::Intent)
+            .run_if(in_state(GameState::Playing)));
     }
 }
\ No newline at end of file
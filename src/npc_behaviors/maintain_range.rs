@@ -1,6 +1,6 @@
 // maintain a certain range to a target
 use bevy::prelude::*;
-use crate::{physics::DesiredDirection, state::GameState};
+use crate::{physics::{DesiredDirection, SimulationSet}, state::GameState};
 
 #[derive(Component)]
 pub struct MaintainRangeFromTarget {
@@ -38,6 +38,8 @@ pub struct MaintainRangePlugin;
 
 impl Plugin for MaintainRangePlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, maintain_range_system.run_if(in_state(GameState::Playing)));
+        app.add_systems(FixedUpdate, maintain_range_system
+            .in_set(SimulationSet::Intent)
+            .run_if(in_state(GameState::Playing)));
     }
 }
\ No newline at end of file
@@ -1,22 +1,24 @@
 //firing a projectile at a target
 
 use bevy::prelude::*;
-use crate::{combat::{spawn_enemy_projectile, ProjectileConfig}, state::GameState};
+use crate::{combat::{fire_pattern, FirePattern, ProjectileConfig, Shooter, ShotOwner, Stunned}, physics::SimulationSet, state::GameState};
 
 #[derive(Component)]
 pub struct ShootAtTarget {
     pub target: Entity,
     pub cooldown: Timer,
     pub config: ProjectileConfig,
+    /// Charge patterns always fire fully charged
+    pub pattern: FirePattern,
 }
 
 fn shoot_at_target_system(
     mut commands: Commands,
     time: Res<Time>,
     targets: Query<&Transform>,
-    mut shooters: Query<(&mut ShootAtTarget, &Transform)>,
+    mut shooters: Query<(Entity, &mut ShootAtTarget, &Transform), Without<Stunned>>,
 ) {
-    for (mut shoot, transform) in &mut shooters {
+    for (entity, mut shoot, transform) in &mut shooters {
         shoot.cooldown.tick(time.delta());
 
         if shoot.cooldown.just_finished() {
@@ -26,12 +28,12 @@ fn shoot_at_target_system(
                     .truncate()
                     .normalize_or_zero();
 
-                spawn_enemy_projectile(
-                    &mut commands,
-                    transform.translation.truncate(),
-                    direction,
-                    &shoot.config,
-                );
+                let shooter = Shooter {
+                    entity,
+                    position: transform.translation.truncate(),
+                    owner: ShotOwner::Enemy,
+                };
+                fire_pattern(&mut commands, &shooter, direction, shoot.pattern, &shoot.config, 1.0);
             }
         }
     }
@@ -41,6 +43,8 @@ pub struct ShootAtTargetPlugin;
 
 impl Plugin for ShootAtTargetPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, shoot_at_target_system.run_if(in_state(GameState::Playing)));
+        app.add_systems(FixedUpdate, shoot_at_target_system
+            .in_set(SimulationSet::Intent)
+            .run_if(in_state(GameState::Playing)));
     }
 }
\ No newline at end of file
@@ -2,12 +2,31 @@
 mod velocity;
 mod hitbox;
 mod movement_styles;
+mod interpolation;
 
 pub use velocity::*;
 pub use hitbox::*;
 pub use movement_styles::*;
+pub use interpolation::*;
 
 use bevy::prelude::*;
+use crate::cli::CliArgs;
+
+/// Simulation steps per second when --tick-rate isn't given
+pub const DEFAULT_TICK_RATE: f64 = 60.0;
+
+/// Order of the gameplay simulation inside FixedUpdate
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SimulationSet {
+    /// Behaviors and player input pick where things want to go / shoot
+    Intent,
+    /// Velocities and positions are updated
+    Movement,
+    /// Overlap checks, sends DamageEvents / PickupEvents
+    Collision,
+    /// Damage and deaths are applied
+    Damage,
+}
 
 /// Set by targeting behaviors (FollowTarget, MaintainRange, etc.)
 /// Read by movement styles (DirectMovement, ZigZag, etc.) to set Velocity
@@ -20,7 +39,22 @@ impl Plugin for PhysicsPlugin {
     fn build(&self, app: &mut App) {
         app
             .add_plugins(velocity::VelocityPlugin)
-            .add_plugins(movement_styles::MovementStylesPlugin);
-            // debug hitbox viewing.add_plugins(hitbox::HitboxPlugin);
+            .add_plugins(movement_styles::MovementStylesPlugin)
+            .add_plugins(interpolation::InterpolationPlugin)
+            .configure_sets(FixedUpdate, (
+                SimulationSet::Intent,
+                SimulationSet::Movement,
+                SimulationSet::Collision,
+                SimulationSet::Damage,
+            ).chain())
+            .insert_resource(Time::<Fixed>::from_hz(DEFAULT_TICK_RATE))
+            .add_systems(Startup, configure_tick_rate);
+    }
+}
+
+fn configure_tick_rate(cli: Res<CliArgs>, mut fixed_time: ResMut<Time<Fixed>>) {
+    if let Some(hz) = cli.tick_rate {
+        fixed_time.set_timestep_hz(hz);
+        info!("Simulation tick rate: {} Hz", hz);
     }
 }
\ No newline at end of file
@@ -1,31 +1,386 @@
 use bevy::prelude::*;
 
-#[derive(Component)]
-pub struct CircleHitBox{
-    pub radius: f32,
+use crate::ascii_sprite::AsciiSprite;
+
+/// Advance width of one IBM Plex Mono character, as a fraction of the font size
+const MONO_CHAR_WIDTH: f32 = 0.6;
+
+/// How much longer than tall (or taller than long) text has to be before it gets a capsule
+const CAPSULE_ASPECT: f32 = 1.5;
+
+/// Shape used for overlap tests, centered on the entity's Transform
+#[derive(Component, Clone, Copy, Debug)]
+pub enum Collider {
+    Circle { radius: f32 },
+    /// Axis-aligned box
+    Aabb { half_size: Vec2 },
+    /// Pill shape: the segment from -half_segment to +half_segment, grown by radius
+    Capsule { half_segment: Vec2, radius: f32 },
+}
+
+/// A collider at a position in the world. Circles are capsules with a zero length segment
+/// so there are only three pairs to test
+enum PlacedShape {
+    Capsule { start: Vec2, end: Vec2, radius: f32 },
+    Aabb { min: Vec2, max: Vec2 },
+}
+
+impl Collider {
+    pub fn circle(radius: f32) -> Self {
+        Collider::Circle { radius }
+    }
+
+    /// Sized to the text an AsciiSprite draws: a circle for roughly square glyphs,
+    /// a capsule for wide ones like `<{=}>` (or tall ones)
+    pub fn fit_ascii(sprite: &AsciiSprite) -> Self {
+        let size = ascii_text_size(sprite);
+        let half = size / 2.0;
+
+        if size.x > size.y * CAPSULE_ASPECT {
+            Collider::Capsule { half_segment: Vec2::new(half.x - half.y, 0.0), radius: half.y }
+        } else if size.y > size.x * CAPSULE_ASPECT {
+            Collider::Capsule { half_segment: Vec2::new(0.0, half.y - half.x), radius: half.x }
+        } else {
+            Collider::Circle { radius: half.max_element() }
+        }
+    }
+
+    /// Radius of a circle around pos that fits the whole shape
+    pub fn bounding_radius(&self) -> f32 {
+        match *self {
+            Collider::Circle { radius } => radius,
+            Collider::Aabb { half_size } => half_size.length(),
+            Collider::Capsule { half_segment, radius } => half_segment.length() + radius,
+        }
+    }
+
+    fn placed(&self, pos: Vec2) -> PlacedShape {
+        match *self {
+            Collider::Circle { radius } => PlacedShape::Capsule { start: pos, end: pos, radius },
+            Collider::Aabb { half_size } => PlacedShape::Aabb { min: pos - half_size, max: pos + half_size },
+            Collider::Capsule { half_segment, radius } => PlacedShape::Capsule {
+                start: pos - half_segment,
+                end: pos + half_segment,
+                radius,
+            },
+        }
+    }
+
+    pub fn overlaps(&self, pos: Vec2, other: &Collider, other_pos: Vec2) -> bool {
+        if let (Collider::Circle { radius }, Collider::Circle { radius: other_radius }) = (self, other) {
+            return circles_overlap(pos, *radius, other_pos, *other_radius);
+        }
+        shapes_overlap(&self.placed(pos), &other.placed(other_pos))
+    }
+
+    /// Swept test for fast movers: this collider over its whole move from `start` to `end`
+    /// against `other` (treated as standing still), so it can't skip over it between two steps.
+    /// The mover is treated as a circle of its bounding radius.
+    /// Returns how far along the move (0.0 - 1.0) it first touched other, 0 if it started
+    /// out touching, so callers hitting several things can pick the first one in the way.
+    pub fn swept_overlap(&self, start: Vec2, end: Vec2, other: &Collider, other_pos: Vec2) -> Option<f32> {
+        let radius = self.bounding_radius();
+        let along = end - start;
+        // grow other by the mover's radius and sweep a point through it instead
+        match other.placed(other_pos) {
+            PlacedShape::Capsule { start: other_start, end: other_end, radius: other_radius } => {
+                ray_capsule_entry(start, along, other_start, other_end, radius + other_radius)
+            }
+            PlacedShape::Aabb { min, max } => ray_rounded_box_entry(start, along, min, max, radius),
+        }
+    }
+}
+
+/// Width and height of the text an AsciiSprite draws (one line per \n)
+pub fn ascii_text_size(sprite: &AsciiSprite) -> Vec2 {
+    let columns = sprite.glyph.lines().map(|line| line.chars().count()).max().unwrap_or(0);
+    let rows = sprite.glyph.lines().count().max(1);
+    Vec2::new(
+        columns as f32 * sprite.font_size * MONO_CHAR_WIDTH,
+        rows as f32 * sprite.font_size,
+    )
 }
 
 pub fn circles_overlap(pos_a: Vec2, radius_a: f32, pos_b: Vec2, radius_b: f32) -> bool {
     pos_a.distance(pos_b) < radius_a + radius_b
 }
 
-pub fn debug_draw_circle_hitboxes(
+/// How far along the segment start -> end (0.0 - 1.0) the closest point to `point` is
+pub fn closest_fraction_on_segment(start: Vec2, end: Vec2, point: Vec2) -> f32 {
+    let along = end - start;
+    let length_squared = along.length_squared();
+    if length_squared == 0.0 {
+        return 0.0;
+    }
+    ((point - start).dot(along) / length_squared).clamp(0.0, 1.0)
+}
+
+fn point_segment_distance(point: Vec2, start: Vec2, end: Vec2) -> f32 {
+    let closest = start.lerp(end, closest_fraction_on_segment(start, end, point));
+    point.distance(closest)
+}
+
+fn point_box_distance(point: Vec2, min: Vec2, max: Vec2) -> f32 {
+    point.distance(point.clamp(min, max))
+}
+
+/// Proper crossing only, segments that just touch end to end come out as distance 0 anyway
+fn segments_cross(a_start: Vec2, a_end: Vec2, b_start: Vec2, b_end: Vec2) -> bool {
+    let a = a_end - a_start;
+    let b = b_end - b_start;
+    let side = |dir: Vec2, from: Vec2, point: Vec2| dir.perp_dot(point - from);
+
+    side(a, a_start, b_start) * side(a, a_start, b_end) < 0.0
+        && side(b, b_start, a_start) * side(b, b_start, a_end) < 0.0
+}
+
+fn segment_segment_distance(a_start: Vec2, a_end: Vec2, b_start: Vec2, b_end: Vec2) -> f32 {
+    if segments_cross(a_start, a_end, b_start, b_end) {
+        return 0.0;
+    }
+    // if they don't cross the closest pair always includes one of the four end points
+    point_segment_distance(a_start, b_start, b_end)
+        .min(point_segment_distance(a_end, b_start, b_end))
+        .min(point_segment_distance(b_start, a_start, a_end))
+        .min(point_segment_distance(b_end, a_start, a_end))
+}
+
+/// Slab test, does the segment pass through the box at all
+fn segment_hits_box(start: Vec2, end: Vec2, min: Vec2, max: Vec2) -> bool {
+    ray_box_entry(start, end - start, min, max).is_some()
+}
+
+/// How far along start -> start + along (0.0 - 1.0) the point first gets inside the box
+fn ray_box_entry(start: Vec2, along: Vec2, min: Vec2, max: Vec2) -> Option<f32> {
+    let (mut enter, mut exit) = (0.0_f32, 1.0_f32);
+
+    for axis in 0..2 {
+        if along[axis].abs() < f32::EPSILON {
+            // parallel to this slab, has to already be inside it
+            if start[axis] < min[axis] || start[axis] > max[axis] {
+                return None;
+            }
+            continue;
+        }
+        let near = (min[axis] - start[axis]) / along[axis];
+        let far = (max[axis] - start[axis]) / along[axis];
+        enter = enter.max(near.min(far));
+        exit = exit.min(near.max(far));
+        if enter > exit {
+            return None;
+        }
+    }
+    Some(enter)
+}
+
+/// How far along start -> start + along (0.0 - 1.0) the point first gets inside the circle
+fn ray_circle_entry(start: Vec2, along: Vec2, center: Vec2, radius: f32) -> Option<f32> {
+    let offset = start - center;
+    if offset.length_squared() < radius * radius {
+        return Some(0.0);
+    }
+    let length = along.length();
+    if length == 0.0 {
+        return None;
+    }
+    let direction = along / length;
+    // distances along and across the move, measured separately so long moves stay accurate
+    let ahead = -offset.dot(direction);
+    let miss = offset.perp_dot(direction).abs();
+    if ahead <= 0.0 || miss >= radius {
+        return None;
+    }
+    let entry = (ahead - (radius * radius - miss * miss).sqrt()) / length;
+    (entry <= 1.0).then_some(entry)
+}
+
+/// A capsule is its two end circles plus the box between them, the point gets into the
+/// capsule when it gets into the first of those
+fn ray_capsule_entry(start: Vec2, along: Vec2, capsule_start: Vec2, capsule_end: Vec2, radius: f32) -> Option<f32> {
+    let ends = [capsule_start, capsule_end].map(|center| ray_circle_entry(start, along, center, radius));
+
+    let segment = capsule_end - capsule_start;
+    let length = segment.length();
+    let middle = (length > 0.0).then(|| {
+        // in the capsule's own frame the box is axis aligned
+        let (x_axis, y_axis) = (segment / length, segment.perp() / length);
+        let local = |point: Vec2| Vec2::new(point.dot(x_axis), point.dot(y_axis));
+        ray_box_entry(local(start - capsule_start), local(along), Vec2::new(0.0, -radius), Vec2::new(length, radius))
+    }).flatten();
+
+    ends.into_iter().chain([middle]).flatten().reduce(f32::min)
+}
+
+/// The box grown by radius with rounded corners: two boxes, one grown sideways and one grown
+/// up and down, plus a circle on each corner
+fn ray_rounded_box_entry(start: Vec2, along: Vec2, min: Vec2, max: Vec2, radius: f32) -> Option<f32> {
+    let corners = [min, Vec2::new(max.x, min.y), max, Vec2::new(min.x, max.y)];
+    let wide = Vec2::new(radius, 0.0);
+    let tall = Vec2::new(0.0, radius);
+
+    corners.into_iter()
+        .map(|corner| ray_circle_entry(start, along, corner, radius))
+        .chain([
+            ray_box_entry(start, along, min - wide, max + wide),
+            ray_box_entry(start, along, min - tall, max + tall),
+        ])
+        .flatten()
+        .reduce(f32::min)
+}
+
+fn segment_box_distance(start: Vec2, end: Vec2, min: Vec2, max: Vec2) -> f32 {
+    if segment_hits_box(start, end, min, max) {
+        return 0.0;
+    }
+    // same idea as segment_segment_distance: closest is a segment end or a box corner
+    let corners = [min, Vec2::new(max.x, min.y), max, Vec2::new(min.x, max.y)];
+    corners.into_iter()
+        .map(|corner| point_segment_distance(corner, start, end))
+        .fold(
+            point_box_distance(start, min, max).min(point_box_distance(end, min, max)),
+            f32::min,
+        )
+}
+
+fn shapes_overlap(a: &PlacedShape, b: &PlacedShape) -> bool {
+    match (a, b) {
+        (
+            PlacedShape::Capsule { start, end, radius },
+            PlacedShape::Capsule { start: other_start, end: other_end, radius: other_radius },
+        ) => segment_segment_distance(*start, *end, *other_start, *other_end) < radius + other_radius,
+        (PlacedShape::Capsule { start, end, radius }, PlacedShape::Aabb { min, max })
+        | (PlacedShape::Aabb { min, max }, PlacedShape::Capsule { start, end, radius }) => {
+            segment_box_distance(*start, *end, *min, *max) < *radius
+        }
+        (PlacedShape::Aabb { min, max }, PlacedShape::Aabb { min: other_min, max: other_max }) => {
+            min.x < other_max.x && other_min.x < max.x && min.y < other_max.y && other_min.y < max.y
+        }
+    }
+}
+
+pub fn debug_draw_hitboxes(
     mut gizmos: Gizmos,
-    query: Query<(&Transform, &CircleHitBox)>,
+    query: Query<(&Transform, &Collider)>,
 ) {
-    for (transform, hitbox) in &query {
-        gizmos.circle_2d(
-            transform.translation.truncate(),  // center position
-            hitbox.radius,                      // radius
-            Color::srgb(0.0, 1.0, 0.0),         // green
-        );
+    let color = Color::srgb(0.0, 1.0, 0.0); // green
+    for (transform, collider) in &query {
+        let pos = transform.translation.truncate();
+        match *collider {
+            Collider::Circle { radius } => {
+                gizmos.circle_2d(pos, radius, color);
+            }
+            Collider::Aabb { half_size } => {
+                gizmos.rect_2d(pos, half_size * 2.0, color);
+            }
+            Collider::Capsule { half_segment, radius } => {
+                // end caps plus the two straight sides
+                let side = half_segment.perp().normalize_or_zero() * radius;
+                gizmos.circle_2d(pos - half_segment, radius, color);
+                gizmos.circle_2d(pos + half_segment, radius, color);
+                gizmos.line_2d(pos - half_segment + side, pos + half_segment + side, color);
+                gizmos.line_2d(pos - half_segment - side, pos + half_segment - side, color);
+            }
+        }
     }
 }
 
-pub struct HitboxPlugin;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CIRCLE: Collider = Collider::Circle { radius: 1.0 };
+    const BOX: Collider = Collider::Aabb { half_size: Vec2::new(1.0, 1.0) };
+    /// Standing up, from (0, -5) to (0, 5)
+    const CAPSULE: Collider = Collider::Capsule { half_segment: Vec2::new(0.0, 5.0), radius: 1.0 };
 
-impl Plugin for HitboxPlugin {
-    fn build(&self, app: &mut App) {
-        app.add_systems(Update, debug_draw_circle_hitboxes);
+    fn assert_close(actual: Option<f32>, expected: f32) {
+        let actual = actual.expect("expected a hit");
+        assert!((actual - expected).abs() < 1e-4, "expected {}, got {}", expected, actual);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn overlaps_each_pair() {
+        assert!(CIRCLE.overlaps(Vec2::ZERO, &CIRCLE, Vec2::new(1.9, 0.0)));
+        assert!(!CIRCLE.overlaps(Vec2::ZERO, &CIRCLE, Vec2::new(2.1, 0.0)));
+
+        assert!(CIRCLE.overlaps(Vec2::ZERO, &BOX, Vec2::new(1.9, 0.0)));
+        // past the corner, inside the boxes' bounds but outside the circle's
+        assert!(!CIRCLE.overlaps(Vec2::ZERO, &BOX, Vec2::new(1.8, 1.8)));
+
+        assert!(BOX.overlaps(Vec2::ZERO, &BOX, Vec2::new(1.9, 1.9)));
+        assert!(!BOX.overlaps(Vec2::ZERO, &BOX, Vec2::new(2.1, 0.0)));
+
+        assert!(CIRCLE.overlaps(Vec2::ZERO, &CAPSULE, Vec2::new(1.9, 4.0)));
+        assert!(!CIRCLE.overlaps(Vec2::ZERO, &CAPSULE, Vec2::new(0.0, 7.1)));
+
+        assert!(CAPSULE.overlaps(Vec2::ZERO, &BOX, Vec2::new(0.0, 6.9)));
+        assert!(!CAPSULE.overlaps(Vec2::ZERO, &BOX, Vec2::new(2.1, 0.0)));
+    }
+
+    #[test]
+    fn swept_circle_hits_at_first_contact() {
+        // touches when its center is at x = -2, 8 of the 20 units in
+        let hit = CIRCLE.swept_overlap(Vec2::new(-10.0, 0.0), Vec2::new(10.0, 0.0), &CIRCLE, Vec2::ZERO);
+        assert_close(hit, 0.4);
+    }
+
+    #[test]
+    fn swept_box_hits_at_first_contact() {
+        let hit = CIRCLE.swept_overlap(Vec2::new(-10.0, 0.0), Vec2::new(10.0, 0.0), &BOX, Vec2::ZERO);
+        assert_close(hit, 0.4);
+        // going straight past a corner only touches its rounded edge
+        let corner = CIRCLE.swept_overlap(Vec2::new(-10.0, 1.5), Vec2::new(10.0, 1.5), &BOX, Vec2::ZERO);
+        let expected = (10.0 - (1.0 + (1.0_f32 - 0.25).sqrt())) / 20.0;
+        assert_close(corner, expected);
+        assert_eq!(CIRCLE.swept_overlap(Vec2::new(-10.0, 2.1), Vec2::new(10.0, 2.1), &BOX, Vec2::ZERO), None);
+    }
+
+    #[test]
+    fn swept_capsule_hits_side_and_end() {
+        let side = CIRCLE.swept_overlap(Vec2::new(-10.0, 3.0), Vec2::new(10.0, 3.0), &CAPSULE, Vec2::ZERO);
+        assert_close(side, 0.4);
+        // from below, touches the end cap when its center is at y = -7
+        let end = CIRCLE.swept_overlap(Vec2::new(0.0, -20.0), Vec2::new(0.0, 20.0), &CAPSULE, Vec2::ZERO);
+        assert_close(end, 13.0 / 40.0);
+        // slanted capsule, same answer as the standing one turned round
+        let slanted = Collider::Capsule { half_segment: Vec2::new(5.0, 5.0).normalize() * 5.0, radius: 1.0 };
+        let hit = CIRCLE.swept_overlap(Vec2::new(-10.0, 10.0), Vec2::new(10.0, -10.0), &slanted, Vec2::ZERO);
+        let expected = (Vec2::new(-10.0, 10.0).length() - 2.0) / Vec2::new(20.0, -20.0).length();
+        assert_close(hit, expected);
+    }
+
+    #[test]
+    fn swept_big_target_further_along_is_hit_first() {
+        let start = Vec2::new(-10.0, 0.0);
+        let end = Vec2::new(10.0, 0.0);
+        // the small box's center comes first, but the big circle's edge is in the way before it
+        let small = CIRCLE.swept_overlap(start, end, &BOX, Vec2::new(-4.0, 0.0)).unwrap();
+        let big = CIRCLE.swept_overlap(start, end, &Collider::circle(8.0), Vec2::new(1.0, 0.0)).unwrap();
+        assert!(big < small);
+        assert_close(Some(big), 0.1);
+    }
+
+    #[test]
+    fn fast_mover_does_not_tunnel() {
+        let tiny = Collider::circle(0.5);
+        for target in [CIRCLE, BOX, CAPSULE] {
+            let hit = tiny.swept_overlap(Vec2::new(-1000.0, 0.0), Vec2::new(1000.0, 0.0), &target, Vec2::ZERO);
+            assert_close(hit, (1000.0 - 1.5) / 2000.0);
+            // neither end of the move overlaps on its own
+            assert!(!tiny.overlaps(Vec2::new(-1000.0, 0.0), &target, Vec2::ZERO));
+            assert!(!tiny.overlaps(Vec2::new(1000.0, 0.0), &target, Vec2::ZERO));
+        }
+    }
+
+    #[test]
+    fn swept_misses_and_starts_inside() {
+        let miss = CIRCLE.swept_overlap(Vec2::new(-10.0, 0.0), Vec2::new(-5.0, 0.0), &CIRCLE, Vec2::ZERO);
+        assert_eq!(miss, None);
+        let away = CIRCLE.swept_overlap(Vec2::new(3.0, 0.0), Vec2::new(10.0, 0.0), &BOX, Vec2::ZERO);
+        assert_eq!(away, None);
+        let inside = CIRCLE.swept_overlap(Vec2::new(0.5, 0.0), Vec2::new(10.0, 0.0), &CAPSULE, Vec2::ZERO);
+        assert_eq!(inside, Some(0.0));
+        let standing_still = CIRCLE.swept_overlap(Vec2::new(1.5, 0.0), Vec2::new(1.5, 0.0), &BOX, Vec2::ZERO);
+        assert_eq!(standing_still, Some(0.0));
+    }
+}
@@ -0,0 +1,87 @@
+//Gameplay moves things in FixedUpdate, which doesn't line up with rendered frames.
+//So sprites don't stutter we draw them between their last two simulated positions.
+use bevy::prelude::*;
+
+/// Smooths the rendered position of something the simulation moves.
+/// Added automatically with Velocity, the player adds it by hand.
+///
+/// Transform holds the real simulated position while FixedUpdate runs and the interpolated
+/// one the rest of the frame. Moving an entity outside FixedUpdate (spawning, teleporting)
+/// is fine, it just snaps there instead of sliding.
+#[derive(Component, Default)]
+pub struct InterpolatedTransform {
+    /// Simulated position before the latest fixed step
+    previous: Vec3,
+    /// Simulated position after the latest fixed step
+    current: Vec3,
+    /// What we last wrote into Transform, None until the entity has been drawn once
+    rendered: Option<Vec3>,
+}
+
+impl InterpolatedTransform {
+    fn snap(&mut self, translation: Vec3) {
+        self.previous = translation;
+        self.current = translation;
+    }
+}
+
+/// Before the fixed steps: put the simulated position back into Transform
+fn restore_simulated_transforms(mut query: Query<(&mut Transform, &mut InterpolatedTransform)>) {
+    for (mut transform, mut interpolated) in &mut query {
+        if interpolated.rendered == Some(transform.translation) {
+            transform.translation = interpolated.current;
+        } else {
+            // moved since we drew it (or brand new)
+            interpolated.snap(transform.translation);
+        }
+    }
+}
+
+/// Start of every fixed step
+fn store_previous_transforms(mut query: Query<(&Transform, &mut InterpolatedTransform)>) {
+    for (transform, mut interpolated) in &mut query {
+        interpolated.previous = transform.translation;
+    }
+}
+
+/// End of every fixed step
+fn store_current_transforms(mut query: Query<(&Transform, &mut InterpolatedTransform)>) {
+    for (transform, mut interpolated) in &mut query {
+        if interpolated.rendered.is_none() {
+            // spawned during this step, don't slide in from wherever `previous` was
+            interpolated.previous = transform.translation;
+        }
+        interpolated.current = transform.translation;
+    }
+}
+
+/// After the fixed steps: draw partway between the last two simulated positions,
+/// by how far we are into the next step
+fn interpolate_transforms(
+    fixed_time: Res<Time<Fixed>>,
+    mut query: Query<(&mut Transform, &mut InterpolatedTransform)>,
+) {
+    let alpha = fixed_time.overstep_fraction();
+
+    for (mut transform, mut interpolated) in &mut query {
+        if interpolated.rendered.is_none() {
+            interpolated.snap(transform.translation);
+        }
+        transform.translation = interpolated.previous.lerp(interpolated.current, alpha);
+        interpolated.rendered = Some(transform.translation);
+    }
+}
+
+pub struct InterpolationPlugin;
+
+impl Plugin for InterpolationPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(RunFixedMainLoop, (
+                restore_simulated_transforms.in_set(RunFixedMainLoopSystems::BeforeFixedMainLoop),
+                interpolate_transforms.in_set(RunFixedMainLoopSystems::AfterFixedMainLoop),
+            ))
+            .add_systems(FixedFirst, store_previous_transforms)
+            .add_systems(FixedLast, store_current_transforms);
+    }
+}
@@ -1,5 +1,5 @@
 use bevy::prelude::*;
-use crate::{physics::{DesiredDirection, Velocity}, state::GameState};
+use crate::{physics::{DesiredDirection, SimulationSet, Velocity, apply_velocity}, state::GameState};
 
 #[derive(Component)]
 pub struct DirectMovement;
@@ -19,6 +19,9 @@ pub struct DirectMovementPlugin;
 
 impl Plugin for DirectMovementPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, move_direct.run_if(in_state(GameState::Playing)));
+        app.add_systems(FixedUpdate, move_direct
+            .in_set(SimulationSet::Movement)
+            .before(apply_velocity)
+            .run_if(in_state(GameState::Playing)));
     }
 }
\ No newline at end of file
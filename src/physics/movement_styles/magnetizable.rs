@@ -2,7 +2,7 @@
 //It should take in a target, a setting for fade in/out of magnetism, and speed at peak
 //IDEA: What if we just lerp the beginning and lerp the end?
 use bevy::prelude::*;
-use crate::{helpers::lerp, physics::{DesiredDirection, Velocity}, state::GameState};
+use crate::{helpers::lerp, physics::{DesiredDirection, SimulationSet, Velocity, apply_velocity}, state::GameState};
 
 /// Marks an entity as magnetized to a target within a range
 #[derive(Component)]
@@ -62,6 +62,9 @@ pub struct MagnetismPlugin;
 
 impl Plugin for MagnetismPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, (magnetized_to_system, magnetic_attraction_system).chain().run_if(in_state(GameState::Playing)));
+        app.add_systems(FixedUpdate, (
+            magnetized_to_system.in_set(SimulationSet::Intent),
+            magnetic_attraction_system.in_set(SimulationSet::Movement).before(apply_velocity),
+        ).run_if(in_state(GameState::Playing)));
     }
 }
\ No newline at end of file
@@ -1,15 +1,18 @@
 use bevy::prelude::*;
+use crate::combat::Stunned;
 use crate::state::GameState;
+use super::{InterpolatedTransform, SimulationSet};
 
 #[derive(Component)]
+#[require(InterpolatedTransform)]
 pub struct Velocity{
     pub speed: f32,
     pub direction: Vec2,
 }
 
-fn apply_velocity(
+pub fn apply_velocity(
     time: Res<Time>,
-    mut query: Query<(&Velocity, &mut Transform)>,
+    mut query: Query<(&Velocity, &mut Transform), Without<Stunned>>,
 ) {
     for (velocity, mut transform) in &mut query {
         let delta = velocity.direction * velocity.speed * time.delta_secs();
@@ -22,6 +25,8 @@ pub struct VelocityPlugin;
 
 impl Plugin for VelocityPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, apply_velocity.run_if(in_state(GameState::Playing)));
+        app.add_systems(FixedUpdate, apply_velocity
+            .in_set(SimulationSet::Movement)
+            .run_if(in_state(GameState::Playing)));
     }
 }
\ No newline at end of file
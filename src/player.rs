@@ -2,8 +2,14 @@ pub mod movement;
 pub mod shoot;
 pub mod interact;
 pub mod weapon_switch;
+pub mod reload;
+pub mod abilities;
+pub mod quick_use;
+pub mod buffs;
 
 use bevy::prelude::*;
+use crate::combat::fire_bursts;
+use crate::physics::SimulationSet;
 use crate::state::GameState;
 
 #[derive(Component)]
@@ -13,11 +19,22 @@ pub struct PlayerPlugin;
 
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
+        app.add_systems(FixedUpdate, (
+            (movement::move_player, abilities::dash_movement).in_set(SimulationSet::Movement),
+            (abilities::tick_ability_cooldowns, abilities::use_abilities).chain().in_set(SimulationSet::Intent),
+            (reload::tick_weapon_timers, shoot::player_shoot).chain().in_set(SimulationSet::Intent),
+            weapon_switch::cancel_holstered_burst.before(fire_bursts).in_set(SimulationSet::Intent),
+            buffs::tick_buffs.in_set(SimulationSet::Intent),
+        ).run_if(in_state(GameState::Playing)));
+        // just_pressed input has to be read every frame, not every fixed step
         app.add_systems(Update, (
-            movement::move_player.run_if(in_state(GameState::Playing)),
-            shoot::player_shoot.run_if(in_state(GameState::Playing)),
             interact::player_interact.run_if(in_state(GameState::Playing)),
+            interact::drop_weapon.run_if(in_state(GameState::Playing)),
             weapon_switch::weapon_switch.run_if(in_state(GameState::Playing)),
+            reload::reload_input.run_if(in_state(GameState::Playing)),
+            abilities::ability_input.run_if(in_state(GameState::Playing)),
+            quick_use::quick_use.run_if(in_state(GameState::Playing)),
+            (abilities::draw_ability_effects, abilities::draw_stunned).run_if(in_state(GameState::Playing)),
         ));
     }
 }
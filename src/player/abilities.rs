@@ -0,0 +1,278 @@
+//Dash, melee swipe and EMP. Keys are read every frame (Update) and queued, the
+//abilities themselves go off on the fixed step with the rest of the simulation
+use bevy::prelude::*;
+use crate::camera::CursorWorld;
+use crate::combat::{DamageEvent, Dead, Stunned, apply_stun, make_invulnerable};
+use crate::enemy::Enemy;
+use super::Player;
+use super::movement::movement_input;
+
+pub const DASH_KEY: KeyCode = KeyCode::Space;
+pub const MELEE_KEY: KeyCode = KeyCode::KeyF;
+pub const EMP_KEY: KeyCode = KeyCode::KeyQ;
+
+const DASH_SPEED: f32 = 900.0;
+const DASH_SECS: f32 = 0.15;
+/// a bit longer than the dash itself so you don't land in a bullet
+const DASH_IFRAME_SECS: f32 = 0.25;
+
+const MELEE_RANGE: f32 = 70.0;
+/// Full width of the swipe, in degrees
+const MELEE_ARC_DEGREES: f32 = 120.0;
+const MELEE_DAMAGE: u32 = 2;
+
+const EMP_RADIUS: f32 = 220.0;
+const EMP_STUN_SECS: f32 = 2.5;
+
+/// How long the swipe / EMP ring stays on screen
+const EFFECT_SECS: f32 = 0.15;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Ability {
+    Dash,
+    Melee,
+    Emp,
+}
+
+impl Ability {
+    pub const ALL: [Ability; 3] = [Ability::Dash, Ability::Melee, Ability::Emp];
+
+    pub fn key(&self) -> KeyCode {
+        match self {
+            Ability::Dash => DASH_KEY,
+            Ability::Melee => MELEE_KEY,
+            Ability::Emp => EMP_KEY,
+        }
+    }
+
+    /// Shown in the HUD next to the name
+    pub fn key_label(&self) -> &'static str {
+        match self {
+            Ability::Dash => "Space",
+            Ability::Melee => "F",
+            Ability::Emp => "Q",
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Ability::Dash => "Dash",
+            Ability::Melee => "Melee",
+            Ability::Emp => "EMP",
+        }
+    }
+
+    pub fn cooldown_secs(&self) -> f32 {
+        match self {
+            Ability::Dash => 1.5,
+            Ability::Melee => 0.6,
+            Ability::Emp => 10.0,
+        }
+    }
+
+    fn index(&self) -> usize {
+        *self as usize
+    }
+}
+
+/// Cooldowns of the player's abilities, and the ones pressed since the last fixed step
+#[derive(Component, Default)]
+pub struct Abilities {
+    /// Indexed by Ability, None when ready
+    cooldowns: [Option<Timer>; Ability::ALL.len()],
+    queued: Vec<Ability>,
+}
+
+impl Abilities {
+    pub fn is_ready(&self, ability: Ability) -> bool {
+        self.cooldowns[ability.index()].is_none()
+    }
+
+    /// Seconds until it can be used again, 0 when ready
+    pub fn remaining_secs(&self, ability: Ability) -> f32 {
+        self.cooldowns[ability.index()].as_ref().map_or(0.0, Timer::remaining_secs)
+    }
+}
+
+/// Moving in a straight line at DASH_SPEED, normal movement is ignored until it's over
+#[derive(Component)]
+pub struct Dashing {
+    pub direction: Vec2,
+    pub timer: Timer,
+}
+
+/// Swipe arc / EMP ring drawn on the player for a moment, only for show
+#[derive(Component)]
+pub enum AbilityEffect {
+    Swipe { direction: Vec2, timer: Timer },
+    Pulse { timer: Timer },
+}
+
+// =============================================================================
+// INPUT
+// =============================================================================
+
+pub fn ability_input(
+    input: Res<ButtonInput<KeyCode>>,
+    mut player: Single<&mut Abilities, With<Player>>,
+){
+    for ability in Ability::ALL {
+        if input.just_pressed(ability.key()) && player.is_ready(ability) && !player.queued.contains(&ability) {
+            player.queued.push(ability);
+        }
+    }
+}
+
+// =============================================================================
+// FIXED STEP
+// =============================================================================
+
+pub fn tick_ability_cooldowns(
+    time: Res<Time>,
+    mut player: Single<&mut Abilities, With<Player>>,
+){
+    for cooldown in &mut player.cooldowns {
+        if let Some(timer) = cooldown.as_mut() {
+            timer.tick(time.delta());
+            if timer.is_finished() {
+                *cooldown = None;
+            }
+        }
+    }
+}
+
+pub fn use_abilities(
+    mut commands: Commands,
+    input: Res<ButtonInput<KeyCode>>,
+    cursor_world: Res<CursorWorld>,
+    mut damage_messages: MessageWriter<DamageEvent>,
+    player: Single<(Entity, &Transform, &mut Abilities), With<Player>>,
+    enemies: Query<(Entity, &Transform, Has<Dead>), With<Enemy>>,
+){
+    let (entity, transform, mut abilities) = player.into_inner();
+    if abilities.queued.is_empty() {
+        return;
+    }
+
+    let position = transform.translation.truncate();
+    let living_enemies = || enemies.iter()
+        .filter(|(_, _, dead)| !dead)
+        .map(|(enemy, enemy_transform, _)| (enemy, enemy_transform.translation.truncate()));
+    let aim = cursor_world.0
+        .map(|cursor| (cursor - position).normalize_or_zero())
+        .filter(|aim| *aim != Vec2::ZERO)
+        .unwrap_or(Vec2::X);
+
+    for ability in std::mem::take(&mut abilities.queued) {
+        abilities.cooldowns[ability.index()] = Some(Timer::from_seconds(ability.cooldown_secs(), TimerMode::Once));
+
+        match ability {
+            Ability::Dash => {
+                // dash the way you're walking, or at the cursor if you're standing still
+                let walking = movement_input(&input);
+                let direction = if walking == Vec2::ZERO { aim } else { walking };
+                commands.entity(entity).insert(Dashing {
+                    direction,
+                    timer: Timer::from_seconds(DASH_SECS, TimerMode::Once),
+                });
+                make_invulnerable(&mut commands, entity, DASH_IFRAME_SECS);
+            }
+            Ability::Melee => {
+                let half_arc = (MELEE_ARC_DEGREES / 2.0).to_radians();
+                for (enemy, enemy_position) in living_enemies() {
+                    let offset = enemy_position - position;
+                    if offset.length() <= MELEE_RANGE && aim.angle_to(offset).abs() <= half_arc {
+                        damage_messages.write(DamageEvent { target: enemy, amount: MELEE_DAMAGE });
+                    }
+                }
+                commands.entity(entity).with_child((
+                    Transform::default(),
+                    AbilityEffect::Swipe {
+                        direction: aim,
+                        timer: Timer::from_seconds(EFFECT_SECS, TimerMode::Once),
+                    },
+                ));
+            }
+            Ability::Emp => {
+                for (enemy, enemy_position) in living_enemies() {
+                    if enemy_position.distance(position) <= EMP_RADIUS {
+                        apply_stun(&mut commands, enemy, EMP_STUN_SECS);
+                    }
+                }
+                commands.entity(entity).with_child((
+                    Transform::default(),
+                    AbilityEffect::Pulse { timer: Timer::from_seconds(EFFECT_SECS, TimerMode::Once) },
+                ));
+            }
+        }
+        info!("{} used", ability.name());
+    }
+}
+
+/// Replaces move_player while Dashing
+pub fn dash_movement(
+    mut commands: Commands,
+    time: Res<Time>,
+    player: Single<(Entity, &mut Transform, &mut Dashing), With<Player>>,
+){
+    let (entity, mut transform, mut dashing) = player.into_inner();
+    let delta = dashing.direction * DASH_SPEED * time.delta_secs();
+    transform.translation.x += delta.x;
+    transform.translation.y += delta.y;
+
+    dashing.timer.tick(time.delta());
+    if dashing.timer.is_finished() {
+        commands.entity(entity).remove::<Dashing>();
+    }
+}
+
+// =============================================================================
+// EFFECTS
+// =============================================================================
+
+pub fn draw_ability_effects(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut gizmos: Gizmos,
+    mut effects: Query<(Entity, &GlobalTransform, &mut AbilityEffect)>,
+){
+    for (entity, transform, mut effect) in &mut effects {
+        let center = transform.translation().truncate();
+        let timer = match effect.as_mut() {
+            AbilityEffect::Swipe { timer, .. } | AbilityEffect::Pulse { timer } => timer,
+        };
+        timer.tick(time.delta());
+        if timer.is_finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        let progress = timer.fraction();
+
+        match effect.as_ref() {
+            AbilityEffect::Swipe { direction, .. } => {
+                // arc of points across the swipe, sweeping from one side to the other
+                let half_arc = (MELEE_ARC_DEGREES / 2.0).to_radians();
+                let end = -half_arc + 2.0 * half_arc * progress;
+                let points = (0..=12).map(|i| {
+                    let angle = -half_arc + (end + half_arc) * i as f32 / 12.0;
+                    center + Vec2::from_angle(angle).rotate(*direction) * MELEE_RANGE
+                });
+                gizmos.linestrip_2d(points, Color::WHITE);
+            }
+            AbilityEffect::Pulse { .. } => {
+                let color = Color::srgba(0.3, 0.8, 1.0, 1.0 - progress);
+                gizmos.circle_2d(center, EMP_RADIUS * progress.max(0.1), color);
+            }
+        }
+    }
+}
+
+/// Little ring around stunned drones
+pub fn draw_stunned(
+    mut gizmos: Gizmos,
+    stunned: Query<&GlobalTransform, With<Stunned>>,
+){
+    for transform in &stunned {
+        gizmos.circle_2d(transform.translation().truncate(), 16.0, Color::srgb(0.3, 0.8, 1.0));
+    }
+}
@@ -0,0 +1,81 @@
+//Temporary boosts from stims. Using one while it's still running starts the timer over
+use bevy::prelude::*;
+use super::Player;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Buff {
+    Speed,
+    Damage,
+}
+
+impl Buff {
+    pub const ALL: [Buff; 2] = [Buff::Speed, Buff::Damage];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Buff::Speed => "Speed",
+            Buff::Damage => "Damage",
+        }
+    }
+
+    pub fn duration_secs(&self) -> f32 {
+        match self {
+            Buff::Speed => 8.0,
+            Buff::Damage => 10.0,
+        }
+    }
+
+    /// Movement speed / projectile damage while it's active
+    pub fn multiplier(&self) -> f32 {
+        match self {
+            Buff::Speed => 1.5,
+            Buff::Damage => 2.0,
+        }
+    }
+
+    fn index(&self) -> usize {
+        *self as usize
+    }
+}
+
+/// The player's running buffs
+#[derive(Component, Default)]
+pub struct ActiveBuffs {
+    /// Indexed by Buff, None when not active
+    timers: [Option<Timer>; Buff::ALL.len()],
+}
+
+impl ActiveBuffs {
+    pub fn start(&mut self, buff: Buff) {
+        self.timers[buff.index()] = Some(Timer::from_seconds(buff.duration_secs(), TimerMode::Once));
+    }
+
+    pub fn is_active(&self, buff: Buff) -> bool {
+        self.timers[buff.index()].is_some()
+    }
+
+    /// The buff's multiplier while it's active, 1 otherwise
+    pub fn multiplier(&self, buff: Buff) -> f32 {
+        if self.is_active(buff) { buff.multiplier() } else { 1.0 }
+    }
+
+    /// Seconds until it wears off, 0 when not active
+    pub fn remaining_secs(&self, buff: Buff) -> f32 {
+        self.timers[buff.index()].as_ref().map_or(0.0, Timer::remaining_secs)
+    }
+}
+
+pub fn tick_buffs(
+    time: Res<Time>,
+    mut player: Single<&mut ActiveBuffs, With<Player>>,
+){
+    for (buff, timer) in Buff::ALL.into_iter().zip(player.timers.iter_mut()) {
+        if let Some(running) = timer.as_mut() {
+            running.tick(time.delta());
+            if running.is_finished() {
+                *timer = None;
+                info!("{} buff wore off", buff.name());
+            }
+        }
+    }
+}
@@ -1,9 +1,71 @@
 use bevy::prelude::*;
+use crate::combat::WeaponDefinitions;
+use crate::inventory::{
+    Inventory, WeaponPickup, WeaponSlotError, nearest_weapon_pickup, put_weapon,
+    replace_weapon, spawn_weapon_pickup, take_weapon,
+};
+use super::Player;
 
+pub const DROP_WEAPON_KEY: KeyCode = KeyCode::KeyG;
+
+/// E picks up the nearest weapon on the ground. If every slot is full it's swapped for
+/// the weapon in hand, which gets left where the pickup was
 pub fn player_interact(
+    mut commands: Commands,
     input: Res<ButtonInput<KeyCode>>,
+    player: Single<(&Transform, &mut Inventory), With<Player>>,
+    pickups: Query<(Entity, &Transform, &WeaponPickup)>,
+    weapons: Res<WeaponDefinitions>,
 ){
-    if input.just_released(KeyCode::KeyE){
-        info!("player_interact no implemented");
+    if !input.just_released(KeyCode::KeyE){
+        return;
+    }
+
+    let (transform, mut inventory) = player.into_inner();
+    let Some((entity, position, pickup)) = nearest_weapon_pickup(transform.translation.truncate(), pickups.iter()) else {
+        return;
+    };
+    let name = weapons.name(pickup.0.weapon_id);
+
+    match put_weapon(&mut inventory, pickup.0.clone()) {
+        Ok(slot) => {
+            info!("Picked up {} into slot {}", name, slot + 1);
+        }
+        Err(WeaponSlotError::AlreadyOwned) => {
+            info!("Already carrying a {}", name);
+            return;
+        }
+        Err(WeaponSlotError::SlotsFull) => {
+            let slot = inventory.active_weapon_slot;
+            if let Some(old) = replace_weapon(&mut inventory, slot, pickup.0.clone()) {
+                info!("Swapped {} for {}", weapons.name(old.weapon_id), name);
+                spawn_weapon_pickup(&mut commands, &weapons, old, position);
+            }
+        }
+    }
+    commands.entity(entity).despawn();
+}
+
+/// Leaves the weapon in hand on the ground. The last weapon can't be dropped
+pub fn drop_weapon(
+    mut commands: Commands,
+    input: Res<ButtonInput<KeyCode>>,
+    player: Single<(&Transform, &mut Inventory), With<Player>>,
+    weapons: Res<WeaponDefinitions>,
+){
+    if !input.just_pressed(DROP_WEAPON_KEY) {
+        return;
+    }
+
+    let (transform, mut inventory) = player.into_inner();
+    if inventory.weapon_slots.iter().flatten().count() <= 1 {
+        info!("Can't drop your last weapon");
+        return;
+    }
+
+    let slot = inventory.active_weapon_slot;
+    if let Some(weapon) = take_weapon(&mut inventory, slot) {
+        info!("Dropped {}", weapons.name(weapon.weapon_id));
+        spawn_weapon_pickup(&mut commands, &weapons, weapon, transform.translation.truncate());
     }
 }
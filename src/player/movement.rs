@@ -1,14 +1,34 @@
 use bevy::prelude::*;
 use super::Player;
+use super::abilities::Dashing;
+use super::buffs::{ActiveBuffs, Buff};
 
 pub fn move_player(
     input: Res<ButtonInput<KeyCode>>,
     time: Res<Time>,
-    player_query: Single<&mut Transform, With<Player>>,
+    player_query: Single<&mut Transform, (With<Player>, Without<Dashing>)>,
+    buffs: Single<&ActiveBuffs, With<Player>>,
 )
 {
     let mut player_transform = player_query.into_inner();
 
+    let direction = movement_input(&input);
+    if direction != Vec2::ZERO{
+        let speed = 300.0 * buffs.multiplier(Buff::Speed);
+        let delta = direction * speed * time.delta_secs();
+        //Desired position because in the future there will be collision
+        let desired_pos = Vec2::new(
+            player_transform.translation.x + delta.x,
+            player_transform.translation.y + delta.y,
+        );
+
+        player_transform.translation.x = desired_pos.x;
+        player_transform.translation.y = desired_pos.y;
+    }
+}
+
+/// WASD direction, zero if nothing's held
+pub fn movement_input(input: &ButtonInput<KeyCode>) -> Vec2 {
     let mut direction = Vec2::ZERO;
     if input.pressed(KeyCode::KeyA){
         direction.x -= 1.0;
@@ -22,17 +42,5 @@ pub fn move_player(
     if input.pressed(KeyCode::KeyS){
         direction.y -= 1.0;
     }
-
-    if direction != Vec2::ZERO{
-        let speed = 300.0;
-        let delta = direction.normalize() * speed * time.delta_secs();
-        //Desired position because in the future there will be collision
-        let desired_pos = Vec2::new(
-            player_transform.translation.x + delta.x,
-            player_transform.translation.y + delta.y,
-        );
-
-        player_transform.translation.x = desired_pos.x;
-        player_transform.translation.y = desired_pos.y;
-    }
+    direction.normalize_or_zero()
 }
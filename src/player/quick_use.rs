@@ -0,0 +1,89 @@
+//Quick-use bar. 4-8 use whatever is in that slot, H always goes for a Medkit
+use bevy::prelude::*;
+use crate::building::{BuildGrid, PlaceableConfig, place_in_grid};
+use crate::camera::CursorWorld;
+use crate::combat::{HealEvent, Health, spawn_grenade};
+use crate::difficulty::Difficulty;
+use crate::inventory::{
+    CONSUMABLE_SLOTS, ConsumableType, Inventory, consumable_at_slot, consumable_count, remove_consumable,
+};
+use super::Player;
+use super::buffs::{ActiveBuffs, Buff};
+
+pub const QUICK_USE_KEYS: [KeyCode; CONSUMABLE_SLOTS] = [
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+    KeyCode::Digit6,
+    KeyCode::Digit7,
+    KeyCode::Digit8,
+];
+pub const MEDKIT_KEY: KeyCode = KeyCode::KeyH;
+pub const MEDKIT_HEAL: u32 = 4;
+
+/// Uses up a consumable, unless it wouldn't do anything (full health, no room for a turret)
+pub fn quick_use(
+    mut commands: Commands,
+    input: Res<ButtonInput<KeyCode>>,
+    cursor_world: Res<CursorWorld>,
+    player: Single<(Entity, &Transform, &Health, &mut Inventory, &mut ActiveBuffs), With<Player>>,
+    mut heal_messages: MessageWriter<HealEvent>,
+    mut build_grid: ResMut<BuildGrid>,
+    difficulty: Res<Difficulty>,
+){
+    let (entity, transform, health, mut inventory, mut buffs) = player.into_inner();
+
+    let pressed = if input.just_pressed(MEDKIT_KEY) {
+        Some(ConsumableType::Medkit)
+    } else {
+        QUICK_USE_KEYS.iter()
+            .position(|key| input.just_pressed(*key))
+            .and_then(|slot| consumable_at_slot(&inventory, slot))
+    };
+    let Some(consumable) = pressed else { return };
+
+    if consumable_count(&inventory, consumable) == 0 {
+        info!("No {}s", consumable.name());
+        return;
+    }
+
+    let position = transform.translation.truncate();
+    let used = match consumable {
+        ConsumableType::Medkit => {
+            if health.current >= health.max {
+                info!("Already at full health");
+                false
+            } else {
+                heal_messages.write(HealEvent { target: entity, amount: MEDKIT_HEAL });
+                true
+            }
+        }
+        ConsumableType::Grenade => match cursor_world.0 {
+            Some(aim) => {
+                spawn_grenade(&mut commands, position, aim);
+                true
+            }
+            None => false,
+        },
+        ConsumableType::SpeedStim => {
+            buffs.start(Buff::Speed);
+            true
+        }
+        ConsumableType::DamageStim => {
+            buffs.start(Buff::Damage);
+            true
+        }
+        ConsumableType::TurretKit => {
+            // goes in the build grid cell you're standing in, like a placed turret would
+            let placed = place_in_grid(&mut commands, &mut build_grid, position, &PlaceableConfig::turret(), &difficulty);
+            if placed.is_none() {
+                info!("No room to deploy a turret here");
+            }
+            placed.is_some()
+        }
+    };
+
+    if used {
+        remove_consumable(&mut inventory, consumable);
+        info!("{} used, {} left", consumable.name(), consumable_count(&inventory, consumable));
+    }
+}
@@ -0,0 +1,45 @@
+use bevy::prelude::*;
+use crate::inventory::{Inventory, active_weapon_mut};
+use super::Player;
+
+pub const RELOAD_KEY: KeyCode = KeyCode::KeyR;
+
+/// Manual reload. Emptying the magazine reloads on its own (see player_shoot)
+pub fn reload_input(
+    input: Res<ButtonInput<KeyCode>>,
+    mut player: Single<&mut Inventory, With<Player>>,
+){
+    if !input.just_pressed(RELOAD_KEY) {
+        return;
+    }
+
+    if let Some(weapon) = active_weapon_mut(&mut player)
+        && weapon.start_reload()
+    {
+        info!("Reloading");
+    }
+}
+
+/// Fills the magazine once the reload timer runs out, and counts down the weapon swap.
+/// Runs on the fixed step so both follow game time (slow-mo, pause)
+pub fn tick_weapon_timers(
+    time: Res<Time>,
+    mut player: Single<&mut Inventory, With<Player>>,
+){
+    if let Some(swap) = player.weapon_swap.as_mut() {
+        swap.tick(time.delta());
+        if swap.is_finished() {
+            player.weapon_swap = None;
+        }
+    }
+
+    let Some(weapon) = active_weapon_mut(&mut player) else { return };
+    let Some(timer) = weapon.reload.as_mut() else { return };
+
+    timer.tick(time.delta());
+    if timer.is_finished() {
+        weapon.reload = None;
+        let magazine_size = weapon.magazine_size;
+        weapon.ammo.reload(magazine_size);
+    }
+}
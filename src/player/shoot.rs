@@ -1,76 +1,90 @@
 use bevy::prelude::*;
-use bevy::window::PrimaryWindow;
 use crate::building::BuildMode;
-use crate::camera::GameCamera;
-use crate::combat::{spawn_player_projectile, FirePattern, Weapon};
+use crate::camera::CursorWorld;
+use crate::combat::{fire_pattern, FirePattern, Shooter, ShotOwner};
+use crate::inventory::{Inventory, active_weapon_mut};
 use super::Player;
+use super::buffs::{ActiveBuffs, Buff};
 
 pub fn player_shoot(
     mut commands: Commands,
     time: Res<Time>,
     input: Res<ButtonInput<MouseButton>>,
-    window: Single<&Window, With<PrimaryWindow>>,
-    camera: Single<(&Camera, &GlobalTransform), With<GameCamera>>,
-    player: Single<(&Transform, &mut Weapon), With<Player>>,
+    cursor_world: Res<CursorWorld>,
+    player: Single<(Entity, &Transform, &mut Inventory, &ActiveBuffs), With<Player>>,
     build_mode: Res<BuildMode>,
 ){
-    let (transform, mut weapon) = player.into_inner();
+    let (entity, transform, mut inventory, buffs) = player.into_inner();
+    let swapping = inventory.weapon_swap.is_some();
+    let Some(weapon) = active_weapon_mut(&mut inventory) else { return };
 
-    // Always tick cooldown
+    // Always tick cooldown (only the weapon in hand cools down)
     weapon.fire_cooldown.tick(time.delta());
 
-    // Don't shoot while in build mode
-    if build_mode.selected.is_some() {
+    // Don't shoot while in build mode, switching weapons or reloading
+    if build_mode.selected.is_some() || swapping || weapon.reload.is_some() {
         return;
     }
 
-    // Fire if holding mouse AND cooldown ready
-    if input.pressed(MouseButton::Left) && weapon.fire_cooldown.just_finished() {
-        let (cam, cam_transform) = camera.into_inner();
-
-        /*This was hard for me to wrap my little pea brain around so i will explain
-        * window_cursor position gets where on the screen we clicked, useless on its own.
-        * viewport_to_world translates where we clicked on monitor to that in the world.
-        * It does that by taking the world point that the camera is looking at, and doing
-        * math to what the screen click would equal to to world location. (returns Ray3d)
-        * We then truncate that ray to get the vec 2.
-        * Lastly, we do math to determine a direction in which we point at
-        */
-        if let Some(cursor_world) = window.cursor_position()
-            .and_then(|cursor| cam.viewport_to_world(cam_transform, cursor).ok())
-            .map(|ray| ray.origin.truncate())
-        {
-            let direction = (cursor_world - transform.translation.truncate()).normalize();
+    let held = input.pressed(MouseButton::Left);
+    // Beams fire every step while held. A round goes every cooldown and one shot's damage
+    // is spread over it
+    let beam = matches!(weapon.fire_pattern, FirePattern::Beam { .. });
+    let charge = if let FirePattern::Charge { max_secs, .. } = weapon.fire_pattern {
+        // Charge weapons build up while held and fire on release.
+        // The cooldown is the shortest hold that fires, so tapping doesn't spam shots
+        if held {
+            *weapon.charge.get_or_insert(0.0) += time.delta_secs();
+            return;
+        }
+        match weapon.charge.take() {
+            Some(secs) if secs >= weapon.fire_cooldown.duration().as_secs_f32() => (secs / max_secs).min(1.0),
+            _ => return,
+        }
+    } else if held && (beam || weapon.fire_cooldown.just_finished()) {
+        // Fire if holding mouse AND cooldown ready (beams every step)
+        0.0
+    } else {
+        return;
+    };
 
-            match weapon.fire_pattern {
-                FirePattern::Single => {
-                    spawn_player_projectile(
-                        &mut commands,
-                        transform.translation.truncate(),
-                        direction,
-                        &weapon.config,
-                    );
-                }
-                FirePattern::Spread { count, angle_degrees } => {
-                    let total_rad = angle_degrees.to_radians();
-                    let step = total_rad / (count - 1).max(1) as f32;
-                    let start = -total_rad / 2.0;
+    // cursor world position is worked out in camera.rs (update_cursor_world),
+    // from there we do math to determine a direction in which we point at
+    let Some(cursor_world) = cursor_world.0 else { return };
 
-                    for i in 0..count {
-                        let angle = start + step * i as f32;
-                        let rotated = Vec2::new(
-                            direction.x * angle.cos() - direction.y * angle.sin(),
-                            direction.x * angle.sin() + direction.y * angle.cos(),
-                        );
-                        spawn_player_projectile(
-                            &mut commands,
-                            transform.translation.truncate(),
-                            rotated,
-                            &weapon.config,
-                        );
-                    }
-                }
-            }
+    if weapon.ammo.magazine == 0 {
+        // out of ammo, try to reload instead (does nothing if the reserve is empty too)
+        weapon.start_reload();
+        return;
+    }
+    if !beam || weapon.fire_cooldown.just_finished() {
+        weapon.ammo.magazine -= 1;
+        if weapon.ammo.magazine == 0 {
+            weapon.start_reload();
         }
     }
+
+    let mut config = weapon.config.clone();
+    let damage = config.damage as f32 * buffs.multiplier(Buff::Damage);
+    config.damage = if beam {
+        // no more than a whole shot per step, even with no cooldown
+        let cooldown_secs = weapon.fire_cooldown.duration().as_secs_f32().max(time.delta_secs());
+        weapon.beam_damage += damage * time.delta_secs() / cooldown_secs;
+        let whole = weapon.beam_damage.floor();
+        weapon.beam_damage -= whole;
+        whole as u32
+    } else {
+        damage.round() as u32
+    };
+
+    let position = transform.translation.truncate();
+    let shooter = Shooter { entity, position, owner: ShotOwner::Player(weapon.weapon_id) };
+    fire_pattern(
+        &mut commands,
+        &shooter,
+        (cursor_world - position).normalize_or_zero(),
+        weapon.fire_pattern,
+        &config,
+        charge,
+    );
 }
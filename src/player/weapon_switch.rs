@@ -1,11 +1,11 @@
 use bevy::prelude::*;
-use crate::combat::Weapon;
-use crate::inventory::{Inventory, weapon_at_slot};
+use crate::combat::{BurstFire, ShotOwner};
+use crate::inventory::{Inventory, active_weapon, switch_weapon};
 use super::Player;
 
 pub fn weapon_switch(
     input: Res<ButtonInput<KeyCode>>,
-    mut player: Single<(&mut Weapon, &mut Inventory), With<Player>>,
+    mut player: Single<&mut Inventory, With<Player>>,
 ){
     let slot = if input.just_pressed(KeyCode::Digit1) {
         Some(0)
@@ -18,10 +18,20 @@ pub fn weapon_switch(
     };
 
     if let Some(slot) = slot {
-        let (ref mut weapon, ref mut inventory) = *player;
-        if let Some(weapon_type) = weapon_at_slot(inventory, slot) {
-            inventory.active_weapon_slot = slot;
-            *weapon.as_mut() = Weapon::from_type(weapon_type);
-        }
+        switch_weapon(&mut player, slot);
+    }
+}
+
+/// The rest of a burst is lost when its weapon leaves the player's hand (switched, dropped,
+/// swapped for a pickup), so it can't keep firing from the next weapon
+pub fn cancel_holstered_burst(
+    mut commands: Commands,
+    player: Single<(Entity, &Inventory, &BurstFire), With<Player>>,
+){
+    let (entity, inventory, burst) = player.into_inner();
+    let in_hand = active_weapon(inventory).map(|weapon| weapon.weapon_id);
+    let still_held = matches!(burst.owner, ShotOwner::Player(weapon) if Some(weapon) == in_hand);
+    if inventory.weapon_swap.is_some() || !still_held {
+        commands.entity(entity).remove::<BurstFire>();
     }
 }
@@ -0,0 +1,544 @@
+//! Input recording and replay, for reproducing bugs.
+//!
+//! `--record <file>` writes the run seed/setup and every in-game frame's input
+//! (recorded keys, mouse buttons, cursor world position, scroll wheel, presses of
+//! `ReplayButton` UI buttons and the frame's time step) to a file.
+//! `--replay <file>` starts the same run from the main menu and feeds the recorded input back
+//! into `ButtonInput` / `CursorWorld` / `MouseWheel` / `Interaction`, stepping time by the
+//! recorded deltas instead of the clock, so the run plays out the same way.
+//!
+//! UI buttons without a `ReplayButton` (settings, audio) only change things outside the run
+//! and aren't recorded.
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::time::Duration;
+
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use bevy::ui::UiSystems;
+
+use crate::camera::{CursorWorld, update_cursor_world};
+use crate::cli::CliArgs;
+use crate::difficulty::{Difficulty, DifficultyPreset};
+use crate::game_rng::GameRng;
+use crate::state::{InGame, MenuScreen, RunSetup, RunStarted, StartingLoadout, start_run};
+
+/// 2 added scroll and UI button presses to frames, 3 added player regeneration to the
+/// difficulty and the H key
+const RECORDING_VERSION: u32 = 3;
+/// Flush the file every so often so a crash still leaves a usable recording
+const FLUSH_EVERY_FRAMES: u32 = 60;
+
+/// Every key gameplay reads. Add new hotkeys here or they won't be recorded
+const RECORDED_KEYS: [KeyCode; 26] = [
+    KeyCode::KeyW,
+    KeyCode::KeyA,
+    KeyCode::KeyS,
+    KeyCode::KeyD,
+    KeyCode::KeyB,
+    KeyCode::KeyE,
+    KeyCode::Tab,
+    KeyCode::Escape,
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::KeyR,
+    KeyCode::KeyG,
+    KeyCode::KeyH,
+    // quick-use bar
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+    KeyCode::Digit6,
+    KeyCode::Digit7,
+    KeyCode::Digit8,
+    // abilities
+    KeyCode::Space,
+    KeyCode::KeyF,
+    KeyCode::KeyQ,
+    // time scale debug keys
+    KeyCode::F5,
+    KeyCode::F6,
+    KeyCode::F7,
+    KeyCode::F8,
+];
+const RECORDED_BUTTONS: [MouseButton; 2] = [MouseButton::Left, MouseButton::Right];
+
+/// UI button that changes the run (crafting, resuming), recorded by a name that's the same
+/// every run. Names can't contain ',' or ';'
+#[derive(Component, Clone)]
+pub struct ReplayButton(pub String);
+
+/// Input state for one frame
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct InputFrame {
+    delta: Duration,
+    keys: Vec<KeyCode>,
+    buttons: Vec<MouseButton>,
+    cursor: Option<Vec2>,
+    /// Summed vertical scroll, in lines
+    scroll: f32,
+    /// ReplayButtons pressed this frame
+    ui_presses: Vec<String>,
+}
+
+impl InputFrame {
+    /// "delta_nanos;KeyW,KeyD;Left;120.5,-33;1;craft3"
+    fn to_line(&self) -> String {
+        let keys: Vec<String> = self.keys.iter().map(|key| format!("{:?}", key)).collect();
+        let buttons: Vec<String> = self.buttons.iter().map(|button| format!("{:?}", button)).collect();
+        let cursor = self.cursor.map(|c| format!("{},{}", c.x, c.y)).unwrap_or_default();
+        format!(
+            "{};{};{};{};{};{}",
+            self.delta.as_nanos(),
+            keys.join(","),
+            buttons.join(","),
+            cursor,
+            self.scroll,
+            self.ui_presses.join(","),
+        )
+    }
+
+    fn from_line(line: &str) -> Option<Self> {
+        let mut fields = line.split(';');
+        let delta = Duration::from_nanos(fields.next()?.parse().ok()?);
+        let keys = parse_list(fields.next()?, &RECORDED_KEYS)?;
+        let buttons = parse_list(fields.next()?, &RECORDED_BUTTONS)?;
+        let cursor = match fields.next()? {
+            "" => None,
+            cursor => {
+                let (x, y) = cursor.split_once(',')?;
+                Some(Vec2::new(x.parse().ok()?, y.parse().ok()?))
+            }
+        };
+        let scroll = fields.next()?.parse().ok()?;
+        let ui_presses = match fields.next()? {
+            "" => Vec::new(),
+            presses => presses.split(',').map(str::to_string).collect(),
+        };
+        if fields.next().is_some() {
+            return None;
+        }
+        Some(Self { delta, keys, buttons, cursor, scroll, ui_presses })
+    }
+}
+
+/// Matches names against the Debug names of `options`
+fn parse_list<T: Copy + std::fmt::Debug>(field: &str, options: &[T]) -> Option<Vec<T>> {
+    if field.is_empty() {
+        return Some(Vec::new());
+    }
+    field.split(',')
+        .map(|name| options.iter().copied().find(|option| format!("{:?}", option) == name))
+        .collect()
+}
+
+/// Everything needed to start the same run again
+#[derive(PartialEq, Debug)]
+pub struct RecordingHeader {
+    seed: u64,
+    difficulty: Difficulty,
+    loadout: StartingLoadout,
+}
+
+impl RecordingHeader {
+    fn to_lines(&self) -> String {
+        let d = &self.difficulty;
+        format!(
+            "# Drone Survival input recording\nversion={}\nseed={}\ndifficulty={};{};{};{};{};{};{}\nloadout={}\n",
+            RECORDING_VERSION,
+            self.seed,
+            d.preset.name(), d.drone_health, d.drone_speed, d.drop_amount, d.player_health, d.beacon_charge_time, d.player_regen,
+            self.loadout.name(),
+        )
+    }
+
+    fn parse_difficulty(value: &str) -> Option<Difficulty> {
+        let mut fields = value.split(';');
+        let preset = DifficultyPreset::from_name(fields.next()?)?;
+        let mut next = || fields.next()?.parse::<f32>().ok();
+        let (drone_health, drone_speed, drop_amount, player_health, beacon_charge_time) =
+            (next()?, next()?, next()?, next()?, next()?);
+        Some(Difficulty {
+            preset,
+            drone_health,
+            drone_speed,
+            drop_amount,
+            player_health,
+            beacon_charge_time,
+            player_regen: fields.next()?.parse().ok()?,
+        })
+    }
+}
+
+fn load_recording(path: &str) -> Result<(RecordingHeader, Vec<InputFrame>), String> {
+    let contents = std::fs::read_to_string(path).map_err(|err| err.to_string())?;
+    parse_recording(&contents)
+}
+
+/// Header lines are `key=value`, everything after them is one frame per line
+fn parse_recording(contents: &str) -> Result<(RecordingHeader, Vec<InputFrame>), String> {
+    let mut seed = None;
+    let mut difficulty = None;
+    let mut loadout = None;
+    let mut frames = Vec::new();
+
+    for (number, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        match line.split_once('=') {
+            Some(("version", version)) => {
+                if version.parse::<u32>().ok() != Some(RECORDING_VERSION) {
+                    return Err(format!("unsupported recording version {}", version));
+                }
+            }
+            Some(("seed", value)) => seed = value.parse().ok(),
+            Some(("difficulty", value)) => difficulty = RecordingHeader::parse_difficulty(value),
+            Some(("loadout", value)) => loadout = StartingLoadout::from_name(value),
+            _ => match InputFrame::from_line(line) {
+                Some(frame) => frames.push(frame),
+                // a half-written last line from a crash is fine, anything else isn't
+                None if number + 1 == contents.lines().count() => warn!("Ignoring truncated last frame"),
+                None => return Err(format!("bad frame on line {}", number + 1)),
+            },
+        }
+    }
+
+    let header = RecordingHeader {
+        seed: seed.ok_or("missing seed")?,
+        difficulty: difficulty.ok_or("missing or bad difficulty")?,
+        loadout: loadout.ok_or("missing or bad loadout")?,
+    };
+    Ok((header, frames))
+}
+
+#[derive(Resource, Default)]
+pub enum Replay {
+    #[default]
+    Off,
+    /// Every run started writes a new recording to `path`
+    Recording {
+        path: String,
+        writer: Option<BufWriter<File>>,
+        frames_since_flush: u32,
+    },
+    Playback {
+        header: RecordingHeader,
+        frames: Vec<InputFrame>,
+        index: usize,
+        started: bool,
+    },
+}
+
+fn setup_replay(cli: Res<CliArgs>, mut replay: ResMut<Replay>) {
+    // recordings only hold the seed and run setup, the cheats would be lost or mixed in
+    if (cli.replay.is_some() || cli.record.is_some()) && cli.has_cheats() {
+        error!("--record and --replay can't be used with --wave, --god or --give, ignoring them");
+        return;
+    }
+
+    if let Some(path) = &cli.replay {
+        match load_recording(path) {
+            Ok((header, frames)) => {
+                info!("Replaying {} ({} frames, seed {})", path, frames.len(), header.seed);
+                *replay = Replay::Playback { header, frames, index: 0, started: false };
+            }
+            Err(err) => error!("Couldn't load recording {}: {}", path, err),
+        }
+    } else if let Some(path) = &cli.record {
+        info!("Recording input to {}", path);
+        *replay = Replay::Recording { path: path.clone(), writer: None, frames_since_flush: 0 };
+    }
+}
+
+// =============================================================================
+// RECORDING
+// =============================================================================
+
+/// Opens a fresh file with the run's header whenever a new run starts
+fn begin_recording(
+    mut started_messages: MessageReader<RunStarted>,
+    mut replay: ResMut<Replay>,
+    rng: Res<GameRng>,
+    difficulty: Res<Difficulty>,
+    setup: Res<RunSetup>,
+) {
+    if started_messages.read().count() == 0 {
+        return;
+    }
+    let Replay::Recording { path, writer, frames_since_flush } = replay.as_mut() else { return };
+
+    let header = RecordingHeader { seed: rng.seed(), difficulty: *difficulty, loadout: setup.loadout };
+    *writer = File::create(&*path)
+        .and_then(|file| {
+            let mut file = BufWriter::new(file);
+            file.write_all(header.to_lines().as_bytes())?;
+            Ok(file)
+        })
+        .inspect_err(|err| error!("Couldn't start recording {}: {}", path, err))
+        .ok();
+    *frames_since_flush = 0;
+}
+
+fn record_frame(
+    time: Res<Time<Real>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    buttons: Res<ButtonInput<MouseButton>>,
+    cursor_world: Res<CursorWorld>,
+    mut scroll_messages: MessageReader<MouseWheel>,
+    ui_buttons: Query<(&Interaction, &ReplayButton), Changed<Interaction>>,
+    mut replay: ResMut<Replay>,
+) {
+    let scroll = scroll_messages.read().map(|scroll| scroll.y).sum();
+    let Replay::Recording { writer: Some(writer), frames_since_flush, .. } = replay.as_mut() else { return };
+
+    let frame = InputFrame {
+        delta: time.delta(),
+        keys: RECORDED_KEYS.into_iter().filter(|key| keys.pressed(*key)).collect(),
+        buttons: RECORDED_BUTTONS.into_iter().filter(|button| buttons.pressed(*button)).collect(),
+        cursor: cursor_world.0,
+        scroll,
+        ui_presses: ui_buttons.iter()
+            .filter(|(interaction, _)| **interaction == Interaction::Pressed)
+            .map(|(_, button)| button.0.clone())
+            .collect(),
+    };
+    let _ = writeln!(writer, "{}", frame.to_line());
+
+    *frames_since_flush += 1;
+    if *frames_since_flush >= FLUSH_EVERY_FRAMES {
+        let _ = writer.flush();
+        *frames_since_flush = 0;
+    }
+}
+
+fn flush_recording(mut replay: ResMut<Replay>) {
+    if let Replay::Recording { writer: Some(writer), .. } = replay.as_mut()
+        && let Err(err) = writer.flush()
+    {
+        error!("Couldn't write recording: {}", err);
+    }
+}
+
+// =============================================================================
+// PLAYBACK
+// =============================================================================
+
+/// Starts the recorded run as soon as the main menu shows up
+fn start_playback_run(
+    mut commands: Commands,
+    mut replay: ResMut<Replay>,
+    mut setup: ResMut<RunSetup>,
+) {
+    let Replay::Playback { header, frames, started: started @ false, .. } = replay.as_mut() else { return };
+
+    setup.difficulty = header.difficulty;
+    setup.loadout = header.loadout;
+    setup.seed = Some(header.seed);
+    commands.run_system_cached(start_run);
+    // time for the first frame has to be set before it starts
+    if let Some(first) = frames.first() {
+        commands.insert_resource(TimeUpdateStrategy::ManualDuration(first.delta));
+    }
+    *started = true;
+}
+
+/// Presses / releases `button` so it reads the same as when it was recorded
+fn set_button<T: Copy + Eq + std::hash::Hash + Send + Sync + 'static>(
+    input: &mut ButtonInput<T>,
+    button: T,
+    was_pressed: bool,
+    is_pressed: bool,
+) {
+    input.reset(button);
+    match (was_pressed, is_pressed) {
+        (false, true) => input.press(button),
+        (true, true) => {
+            input.press(button);
+            input.clear_just_pressed(button);
+        }
+        (true, false) => {
+            input.press(button);
+            input.clear_just_pressed(button);
+            input.release(button);
+        }
+        (false, false) => {}
+    }
+}
+
+/// Overwrites this frame's real input with the recorded frame
+fn apply_playback_frame(
+    replay: Res<Replay>,
+    mut keys: ResMut<ButtonInput<KeyCode>>,
+    mut buttons: ResMut<ButtonInput<MouseButton>>,
+    mut cursor_world: ResMut<CursorWorld>,
+    mut scroll_messages: ResMut<Messages<MouseWheel>>,
+) {
+    let Replay::Playback { frames, index, started: true, .. } = replay.as_ref() else { return };
+    let Some(frame) = frames.get(*index) else { return };
+    let previous = index.checked_sub(1).and_then(|i| frames.get(i));
+    let previous = previous.cloned().unwrap_or_default();
+
+    for key in RECORDED_KEYS {
+        set_button(&mut keys, key, previous.keys.contains(&key), frame.keys.contains(&key));
+    }
+    for button in RECORDED_BUTTONS {
+        set_button(&mut buttons, button, previous.buttons.contains(&button), frame.buttons.contains(&button));
+    }
+    cursor_world.0 = frame.cursor;
+
+    scroll_messages.clear();
+    if frame.scroll != 0.0 {
+        scroll_messages.write(MouseWheel { unit: MouseScrollUnit::Line, x: 0.0, y: frame.scroll, window: Entity::PLACEHOLDER });
+    }
+}
+
+/// Presses the recorded ReplayButtons, and undoes any press the real mouse made on them
+fn apply_playback_ui_presses(
+    replay: Res<Replay>,
+    mut ui_buttons: Query<(&mut Interaction, &ReplayButton)>,
+) {
+    let Replay::Playback { frames, index, started: true, .. } = replay.as_ref() else { return };
+    let Some(frame) = frames.get(*index) else { return };
+
+    for (mut interaction, button) in &mut ui_buttons {
+        if frame.ui_presses.contains(&button.0) {
+            // set even when it already reads Pressed, handlers look for Changed
+            *interaction = Interaction::Pressed;
+        } else {
+            interaction.set_if_neq(Interaction::None);
+        }
+    }
+}
+
+/// Moves to the next frame and sets up its time step, handing control back when the recording ends
+fn advance_playback(mut commands: Commands, mut replay: ResMut<Replay>) {
+    let Replay::Playback { frames, index, started: true, .. } = replay.as_mut() else { return };
+
+    *index += 1;
+    match frames.get(*index) {
+        Some(frame) => commands.insert_resource(TimeUpdateStrategy::ManualDuration(frame.delta)),
+        None => {
+            info!("Replay finished after {} frames", frames.len());
+            commands.insert_resource(TimeUpdateStrategy::Automatic);
+            *replay = Replay::Off;
+        }
+    }
+}
+
+/// Leaving the run (game over, victory, main menu) ends playback even if frames are left
+fn stop_playback(mut commands: Commands, mut replay: ResMut<Replay>) {
+    if let Replay::Playback { started: true, frames, index, .. } = replay.as_ref() {
+        if *index < frames.len() {
+            warn!("Run ended with {} recorded frames left, the replay went out of sync", frames.len() - index);
+        }
+        commands.insert_resource(TimeUpdateStrategy::Automatic);
+        *replay = Replay::Off;
+    }
+}
+
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<Replay>()
+            .add_systems(Startup, setup_replay)
+            .add_systems(Update, start_playback_run.run_if(in_state(MenuScreen::Main)))
+            .add_systems(PreUpdate, (
+                apply_playback_frame.after(update_cursor_world).before(UiSystems::Focus),
+                apply_playback_ui_presses.after(UiSystems::Focus),
+            ))
+            .add_systems(Last, (
+                begin_recording,
+                // same condition as recording so frames line up one to one
+                (record_frame, advance_playback).run_if(in_state(InGame::Yes)),
+            ).chain())
+            .add_systems(OnExit(InGame::Yes), (flush_recording, stop_playback));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame() -> InputFrame {
+        InputFrame {
+            delta: Duration::from_nanos(16_666_667),
+            keys: vec![KeyCode::KeyW, KeyCode::KeyD, KeyCode::Digit4],
+            buttons: vec![MouseButton::Left],
+            cursor: Some(Vec2::new(120.5, -33.25)),
+            scroll: -1.0,
+            ui_presses: vec!["craft3".to_string(), "resume".to_string()],
+        }
+    }
+
+    fn header() -> RecordingHeader {
+        RecordingHeader {
+            seed: 0xDEAD_BEEF,
+            difficulty: Difficulty { player_regen: true, drone_speed: 1.35, ..Difficulty::default() },
+            loadout: StartingLoadout::default(),
+        }
+    }
+
+    #[test]
+    fn frame_round_trips() {
+        let frame = frame();
+        assert_eq!(InputFrame::from_line(&frame.to_line()), Some(frame));
+    }
+
+    #[test]
+    fn empty_frame_round_trips() {
+        let frame = InputFrame::default();
+        assert_eq!(InputFrame::from_line(&frame.to_line()), Some(frame));
+    }
+
+    #[test]
+    fn bad_frames_are_rejected() {
+        // unrecorded key
+        assert_eq!(InputFrame::from_line("100;KeyZ;;;0;"), None);
+        // cut off before the scroll field
+        assert_eq!(InputFrame::from_line("100;KeyW;Left;1,2"), None);
+        assert_eq!(InputFrame::from_line("100;;;1;0;"), None);
+        assert_eq!(InputFrame::from_line("100;;;;0;;extra"), None);
+    }
+
+    #[test]
+    fn recording_round_trips() {
+        let frames = vec![frame(), InputFrame::default(), frame()];
+        let mut contents = header().to_lines();
+        for frame in &frames {
+            contents.push_str(&frame.to_line());
+            contents.push('\n');
+        }
+
+        let (parsed_header, parsed_frames) = parse_recording(&contents).unwrap();
+        assert_eq!(parsed_header, header());
+        assert_eq!(parsed_frames, frames);
+    }
+
+    #[test]
+    fn truncated_last_frame_is_dropped() {
+        let mut contents = header().to_lines();
+        contents.push_str(&frame().to_line());
+        contents.push_str("\n16666667;KeyW;Le");
+
+        let (_, frames) = parse_recording(&contents).unwrap();
+        assert_eq!(frames, vec![frame()]);
+    }
+
+    #[test]
+    fn other_versions_are_rejected() {
+        let contents = header().to_lines().replace(&format!("version={}", RECORDING_VERSION), "version=1");
+        assert!(parse_recording(&contents).is_err());
+    }
+
+    #[test]
+    fn difficulty_without_regen_is_rejected() {
+        assert!(RecordingHeader::parse_difficulty("Normal;1;1;1;1;1").is_none());
+        assert!(RecordingHeader::parse_difficulty("Normal;1;1;1;1;1;true").is_some());
+    }
+}
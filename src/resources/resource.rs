@@ -1,6 +1,7 @@
 use bevy::prelude::*;
+use crate::physics::SimulationSet;
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
 pub enum ResourceType {
     ScrapMetal,
     Circuitry,
@@ -8,6 +9,12 @@ pub enum ResourceType {
 }
 
 impl ResourceType {
+    pub const ALL: [ResourceType; 3] = [
+        ResourceType::ScrapMetal,
+        ResourceType::Circuitry,
+        ResourceType::DroneWeaponParts,
+    ];
+
     pub fn name(&self) -> &'static str {
         match self {
             ResourceType::ScrapMetal => "Scrap Metal",
@@ -76,6 +83,6 @@ pub struct ResourcePlugin;
 
 impl Plugin for ResourcePlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, tick_resource_lifetimes);
+        app.add_systems(FixedUpdate, tick_resource_lifetimes.in_set(SimulationSet::Movement));
     }
 }
\ No newline at end of file
@@ -15,6 +15,8 @@ pub struct ResourceRange{
 #[derive(Resource)]
 pub struct DropTable {
     pub table: HashMap<DroneKind, Vec<ResourceRange>>,
+    /// Chance (0-1) of a health pickup on death, on top of the resources
+    pub health_chance: HashMap<DroneKind, f32>,
 }
 
 impl Default for DropTable {
@@ -29,6 +31,12 @@ impl Default for DropTable {
             ResourceRange { resource: ResourceType::ScrapMetal, min: 1, max: 2 },
             ResourceRange { resource: ResourceType::DroneWeaponParts, min: 0, max: 1 },
         ]);
-        Self { table }
+
+        //health pickups are rare, shooters are harder to kill so they drop more often
+        let mut health_chance = HashMap::new();
+        health_chance.insert(DroneKind::Chaser, 0.03);
+        health_chance.insert(DroneKind::Shooter, 0.06);
+
+        Self { table, health_chance }
     }
 }
\ No newline at end of file
@@ -0,0 +1,455 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use crate::cli::CliArgs;
+use crate::difficulty::Difficulty;
+use crate::game_rng::GameRng;
+use crate::replay::Replay;
+use crate::run_stats::RunStats;
+use crate::state::{GameOverReason, GameState};
+
+/// Written to the working directory the game is launched from
+const HISTORY_PATH: &str = "run_history.txt";
+/// Bumped whenever a field is added. Older files still load, missing fields fall back to defaults
+const HISTORY_VERSION: u32 = 2;
+/// Oldest runs are dropped past this
+const MAX_RECORDS: usize = 200;
+
+// Scoring
+const POINTS_PER_WAVE: f32 = 1000.0;
+const POINTS_PER_KILL: f32 = 25.0;
+const POINTS_PER_SECOND: f32 = 2.0;
+const VICTORY_BONUS: f32 = 5000.0;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RunOutcome {
+    Victory,
+    PlayerKilled,
+    BeaconDestroyed,
+}
+
+impl RunOutcome {
+    pub fn name(&self) -> &'static str {
+        match self {
+            RunOutcome::Victory => "Victory",
+            RunOutcome::PlayerKilled => "Killed",
+            RunOutcome::BeaconDestroyed => "Beacon lost",
+        }
+    }
+
+    /// Stable key written to the history file
+    fn key(&self) -> &'static str {
+        match self {
+            RunOutcome::Victory => "victory",
+            RunOutcome::PlayerKilled => "killed",
+            RunOutcome::BeaconDestroyed => "beacon",
+        }
+    }
+
+    fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "victory" => Some(RunOutcome::Victory),
+            "killed" => Some(RunOutcome::PlayerKilled),
+            "beacon" => Some(RunOutcome::BeaconDestroyed),
+            _ => None,
+        }
+    }
+}
+
+/// One finished run, one line in the history file
+#[derive(Clone, Debug)]
+pub struct RunRecord {
+    pub score: u32,
+    pub waves_reached: u32,
+    pub duration_secs: f32,
+    pub outcome: RunOutcome,
+    /// Preset name, "Custom" for hand tuned runs
+    pub difficulty: String,
+    /// Seconds since the unix epoch, 0 if unknown
+    pub finished_at: u64,
+    /// None for runs saved before seeds were recorded (version 1)
+    pub seed: Option<u64>,
+}
+
+impl RunRecord {
+    pub fn from_run(stats: &RunStats, difficulty: &Difficulty, seed: u64, outcome: RunOutcome) -> Self {
+        let waves_done = stats.waves_cleared + stats.waves_overrun;
+        let waves_reached = match outcome {
+            RunOutcome::Victory => waves_done,
+            // died partway through the next wave
+            _ => waves_done + 1,
+        };
+
+        Self {
+            score: score_run(stats, difficulty, outcome),
+            waves_reached,
+            duration_secs: stats.time_survived.elapsed_secs(),
+            outcome,
+            difficulty: difficulty.preset.name().to_string(),
+            finished_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            seed: Some(seed),
+        }
+    }
+
+    /// "score=1234;waves=3;..." - order doesn't matter and unknown keys are ignored
+    fn to_line(&self) -> String {
+        let mut line = format!(
+            "score={};waves={};duration={:.1};outcome={};difficulty={};date={}",
+            self.score,
+            self.waves_reached,
+            self.duration_secs,
+            self.outcome.key(),
+            self.difficulty,
+            self.finished_at,
+        );
+        if let Some(seed) = self.seed {
+            line.push_str(&format!(";seed={}", seed));
+        }
+        line
+    }
+
+    /// Score and outcome are required, everything else has a default
+    fn from_line(line: &str) -> Option<Self> {
+        let mut score = None;
+        let mut outcome = None;
+        let mut record = RunRecord {
+            score: 0,
+            waves_reached: 0,
+            duration_secs: 0.0,
+            outcome: RunOutcome::PlayerKilled,
+            difficulty: "Normal".to_string(),
+            finished_at: 0,
+            seed: None,
+        };
+
+        for field in line.split(';') {
+            let (key, value) = field.split_once('=')?;
+            match key.trim() {
+                "score" => score = Some(value.trim().parse().ok()?),
+                "outcome" => outcome = Some(RunOutcome::from_key(value.trim())?),
+                "waves" => record.waves_reached = value.trim().parse().ok()?,
+                "duration" => record.duration_secs = value.trim().parse().ok()?,
+                "difficulty" => record.difficulty = value.trim().to_string(),
+                "date" => record.finished_at = value.trim().parse().ok()?,
+                "seed" => record.seed = Some(value.trim().parse().ok()?),
+                _ => {} // written by a newer version
+            }
+        }
+
+        record.score = score?;
+        record.outcome = outcome?;
+        Some(record)
+    }
+
+    /// "2026-10-19", or "unknown" for records without a date
+    pub fn date(&self) -> String {
+        if self.finished_at == 0 {
+            return "unknown".to_string();
+        }
+        // days since epoch -> civil date (Howard Hinnant's algorithm)
+        let days = (self.finished_at / 86_400) as i64 + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days.rem_euclid(146_097);
+        let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let mp = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+        format!("{:04}-{:02}-{:02}", year, month, day)
+    }
+}
+
+/// (waves * 1000 + kills * 25 + seconds * 2 + victory bonus) * difficulty multiplier
+pub fn score_run(stats: &RunStats, difficulty: &Difficulty, outcome: RunOutcome) -> u32 {
+    let mut score = stats.waves_cleared as f32 * POINTS_PER_WAVE
+        + stats.total_kills() as f32 * POINTS_PER_KILL
+        + stats.time_survived.elapsed_secs() * POINTS_PER_SECOND;
+    if outcome == RunOutcome::Victory {
+        score += VICTORY_BONUS;
+    }
+    (score * difficulty.score_multiplier()).round() as u32
+}
+
+/// Every finished run, oldest first. Loaded at startup and saved after each run
+#[derive(Resource, Default)]
+pub struct RunHistory {
+    pub records: Vec<RunRecord>,
+    /// The run that just ended when it was kept out of the history (cheats or a replay)
+    pub unranked: Option<RunRecord>,
+}
+
+impl RunHistory {
+    /// Missing file = empty history. Lines that can't be read are skipped, and the original
+    /// file is kept as a backup so the next save doesn't silently throw it away
+    pub fn load() -> Self {
+        let contents = match std::fs::read_to_string(HISTORY_PATH) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Self::default(),
+            Err(err) => {
+                warn!("Couldn't read run history ({}), starting fresh", err);
+                // not text at all, still worth keeping
+                if err.kind() == std::io::ErrorKind::InvalidData {
+                    backup_history_file();
+                }
+                return Self::default();
+            }
+        };
+
+        let (history, skipped) = Self::parse(&contents);
+        if skipped > 0 {
+            warn!("Skipped {} unreadable run history entries", skipped);
+            backup_history_file();
+        }
+        info!("Loaded {} runs from history", history.records.len());
+        history
+    }
+
+    /// The history in `contents` and how many lines couldn't be read
+    fn parse(contents: &str) -> (Self, usize) {
+        let mut records = Vec::new();
+        let mut skipped = 0;
+        for line in contents.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(version) = line.strip_prefix("version=") {
+                if version.parse::<u32>().is_ok_and(|v| v > HISTORY_VERSION) {
+                    warn!("Run history was written by a newer version ({}), unknown fields are ignored", version);
+                }
+                continue;
+            }
+            match RunRecord::from_line(line) {
+                Some(record) => records.push(record),
+                None => skipped += 1,
+            }
+        }
+
+        (Self { records, unranked: None }, skipped)
+    }
+
+    pub fn save(&self) {
+        let mut contents = format!("# Drone Survival run history\nversion={}\n", HISTORY_VERSION);
+        for record in &self.records {
+            contents.push_str(&record.to_line());
+            contents.push('\n');
+        }
+        if let Err(err) = std::fs::write(HISTORY_PATH, contents) {
+            warn!("Couldn't save run history: {}", err);
+        }
+    }
+
+    pub fn add(&mut self, record: RunRecord) {
+        self.records.push(record);
+        if self.records.len() > MAX_RECORDS {
+            let excess = self.records.len() - MAX_RECORDS;
+            self.records.drain(..excess);
+        }
+    }
+
+    /// Highest scores first
+    pub fn top(&self, count: usize) -> Vec<&RunRecord> {
+        let mut sorted: Vec<_> = self.records.iter().collect();
+        sorted.sort_by_key(|record| std::cmp::Reverse(record.score));
+        sorted.truncate(count);
+        sorted
+    }
+
+    /// Newest first
+    pub fn recent(&self, count: usize) -> impl Iterator<Item = &RunRecord> {
+        self.records.iter().rev().take(count)
+    }
+
+    /// The run that just ended, None if it was unranked
+    pub fn latest(&self) -> Option<&RunRecord> {
+        if self.unranked.is_some() {
+            return None;
+        }
+        self.records.last()
+    }
+
+    /// True if the latest run has the highest score so far
+    pub fn latest_is_best(&self) -> bool {
+        self.latest().is_some_and(|latest| self.records.iter().all(|r| r.score <= latest.score))
+    }
+}
+
+/// Copies the history file to the first of run_history.txt.bak, .bak2, .bak3... that doesn't
+/// exist yet, so an older backup is never overwritten
+fn backup_history_file() {
+    let Some(backup) = std::iter::once(format!("{}.bak", HISTORY_PATH))
+        .chain((2..=100).map(|n| format!("{}.bak{}", HISTORY_PATH, n)))
+        .find(|path| !std::path::Path::new(path).exists())
+    else {
+        warn!("Too many run history backups, not making another");
+        return;
+    };
+    match std::fs::copy(HISTORY_PATH, &backup) {
+        Ok(_) => warn!("Kept the unreadable run history as {}", backup),
+        Err(err) => warn!("Couldn't back up run history: {}", err),
+    }
+}
+
+fn load_run_history(mut commands: Commands) {
+    commands.insert_resource(RunHistory::load());
+}
+
+/// Where the run came from, only normal runs go on the leaderboard
+#[derive(SystemParam)]
+pub struct RunOrigin<'w> {
+    cli: Res<'w, CliArgs>,
+    replay: Res<'w, Replay>,
+}
+
+impl RunOrigin<'_> {
+    /// Not started with --wave, --god or --give and not played back from a recording
+    pub fn is_ranked(&self) -> bool {
+        !self.cli.has_cheats() && !matches!(*self.replay, Replay::Playback { .. })
+    }
+}
+
+/// Runs on entering GameOver / Victory, before the end screen is spawned
+pub fn record_run(
+    state: Res<State<GameState>>,
+    reason: Res<GameOverReason>,
+    stats: Res<RunStats>,
+    difficulty: Res<Difficulty>,
+    rng: Res<GameRng>,
+    origin: RunOrigin,
+    mut history: ResMut<RunHistory>,
+) {
+    let outcome = match (state.get(), *reason) {
+        (GameState::Victory, _) => RunOutcome::Victory,
+        (_, GameOverReason::BeaconDestroyed) => RunOutcome::BeaconDestroyed,
+        _ => RunOutcome::PlayerKilled,
+    };
+
+    let record = RunRecord::from_run(&stats, &difficulty, rng.seed(), outcome);
+    info!("Run finished: {} with {} points", outcome.name(), record.score);
+    if !origin.is_ranked() {
+        info!("Cheats or a replay were used, not adding the run to the history");
+        history.unranked = Some(record);
+        return;
+    }
+    history.unranked = None;
+    history.add(record);
+    history.save();
+}
+
+pub struct RunHistoryPlugin;
+
+impl Plugin for RunHistoryPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<RunHistory>()
+            .add_systems(Startup, load_run_history);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(waves_cleared: u32, kills: u32, secs: f32) -> RunStats {
+        let mut stats = RunStats { waves_cleared, ..default() };
+        stats.kills.insert(crate::spawning::DroneKind::Chaser, kills);
+        stats.time_survived.tick(std::time::Duration::from_secs_f32(secs));
+        stats
+    }
+
+    #[test]
+    fn line_round_trips() {
+        let record = RunRecord {
+            score: 1234,
+            waves_reached: 3,
+            duration_secs: 95.5,
+            outcome: RunOutcome::BeaconDestroyed,
+            difficulty: "Hard".to_string(),
+            finished_at: 1_700_000_000,
+            seed: Some(42),
+        };
+        let parsed = RunRecord::from_line(&record.to_line()).unwrap();
+        assert_eq!(parsed.score, 1234);
+        assert_eq!(parsed.waves_reached, 3);
+        assert_eq!(parsed.duration_secs, 95.5);
+        assert_eq!(parsed.outcome, RunOutcome::BeaconDestroyed);
+        assert_eq!(parsed.difficulty, "Hard");
+        assert_eq!(parsed.finished_at, 1_700_000_000);
+        assert_eq!(parsed.seed, Some(42));
+    }
+
+    #[test]
+    fn partial_line_uses_defaults() {
+        let record = RunRecord::from_line("score=500;outcome=killed").unwrap();
+        assert_eq!(record.score, 500);
+        assert_eq!(record.waves_reached, 0);
+        assert_eq!(record.difficulty, "Normal");
+        assert_eq!(record.seed, None);
+    }
+
+    #[test]
+    fn unknown_keys_are_ignored() {
+        let record = RunRecord::from_line("score=10;outcome=victory;from_the_future=yes").unwrap();
+        assert_eq!(record.outcome, RunOutcome::Victory);
+    }
+
+    #[test]
+    fn corrupt_lines_are_rejected() {
+        // missing a required field
+        assert!(RunRecord::from_line("waves=3;outcome=killed").is_none());
+        assert!(RunRecord::from_line("score=10;waves=3").is_none());
+        // bad values
+        assert!(RunRecord::from_line("score=lots;outcome=killed").is_none());
+        assert!(RunRecord::from_line("score=10;outcome=exploded").is_none());
+        assert!(RunRecord::from_line("score=10;outcome=killed;waves=-1").is_none());
+        // not key=value, e.g. cut off halfway through a write
+        assert!(RunRecord::from_line("score=10;outcome=killed;wav").is_none());
+        assert!(RunRecord::from_line("").is_none());
+    }
+
+    #[test]
+    fn parse_skips_bad_lines_and_counts_them() {
+        let contents = "# comment\nversion=2\nscore=10;outcome=killed\ngarbage\n\nscore=20;outcome=victory\n";
+        let (history, skipped) = RunHistory::parse(contents);
+        assert_eq!(history.records.len(), 2);
+        assert_eq!(skipped, 1);
+    }
+
+    #[test]
+    fn unranked_run_is_not_the_latest() {
+        let (mut history, _) = RunHistory::parse("score=10;outcome=killed\n");
+        assert_eq!(history.latest().map(|record| record.score), Some(10));
+
+        history.unranked = RunRecord::from_line("score=99;outcome=victory");
+        assert!(history.latest().is_none());
+        assert!(!history.latest_is_best());
+    }
+
+    #[test]
+    fn score_adds_waves_kills_and_time() {
+        let difficulty = Difficulty::default();
+        let score = score_run(&stats(2, 4, 10.0), &difficulty, RunOutcome::PlayerKilled);
+        assert_eq!(score, 2 * 1000 + 4 * 25 + 10 * 2);
+    }
+
+    #[test]
+    fn victory_adds_bonus() {
+        let difficulty = Difficulty::default();
+        let killed = score_run(&stats(1, 0, 0.0), &difficulty, RunOutcome::PlayerKilled);
+        let victory = score_run(&stats(1, 0, 0.0), &difficulty, RunOutcome::Victory);
+        assert_eq!(victory - killed, 5000);
+    }
+
+    #[test]
+    fn harder_difficulty_scores_more() {
+        let stats = stats(3, 10, 60.0);
+        let normal = score_run(&stats, &Difficulty::default(), RunOutcome::PlayerKilled);
+        let hard = score_run(&stats, &Difficulty::from_preset(crate::difficulty::DifficultyPreset::Hard), RunOutcome::PlayerKilled);
+        assert!(hard > normal);
+    }
+}
@@ -0,0 +1,157 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use bevy::time::Stopwatch;
+
+use crate::building::Structure;
+use crate::combat::{Beam, DeathEvent, HitTaken, PlayerOwned, Projectile, SourceWeapon, WeaponId, apply_damage, apply_death};
+use crate::physics::SimulationSet;
+use crate::player::Player;
+use crate::resources::ResourceType;
+use crate::spawning::{DroneKind, DroneType, WaveCleared, check_wave_clear};
+use crate::state::GameState;
+
+/// Shots fired / hit with one weapon
+#[derive(Default, Clone, Copy)]
+pub struct ShotStats {
+    pub fired: u32,
+    pub hit: u32,
+}
+
+impl ShotStats {
+    /// 0.0 - 1.0, 0 if nothing was fired
+    pub fn accuracy(&self) -> f32 {
+        if self.fired == 0 {
+            0.0
+        } else {
+            self.hit as f32 / self.fired as f32
+        }
+    }
+}
+
+/// Everything that happened during the current run, shown on the end screen.
+/// Reset when a new game is started from run setup.
+#[derive(Resource, Default)]
+pub struct RunStats {
+    /// Only ticks while Playing (not paused / crafting)
+    pub time_survived: Stopwatch,
+    pub waves_cleared: u32,
+    /// Waves that ran out of pressure time before they were cleared
+    pub waves_overrun: u32,
+    pub kills: HashMap<DroneKind, u32>,
+    pub shots: HashMap<WeaponId, ShotStats>,
+    /// Health drones lost to anything but their own explosion, counted in apply_damage
+    pub damage_dealt: u32,
+    pub damage_taken: u32,
+    pub resources_collected: HashMap<ResourceType, u32>,
+    pub structures_built: u32,
+    pub structures_lost: u32,
+}
+
+impl RunStats {
+    pub fn total_kills(&self) -> u32 {
+        self.kills.values().sum()
+    }
+
+    pub fn total_resources(&self) -> u32 {
+        self.resources_collected.values().sum()
+    }
+
+    pub fn record_hit(&mut self, weapon: WeaponId) {
+        self.shots.entry(weapon).or_default().hit += 1;
+    }
+
+    pub fn record_pickup(&mut self, resource: ResourceType, amount: u32) {
+        *self.resources_collected.entry(resource).or_insert(0) += amount;
+    }
+}
+
+fn tick_time_survived(time: Res<Time>, mut stats: ResMut<RunStats>) {
+    stats.time_survived.tick(time.delta());
+}
+
+fn record_waves(mut cleared_messages: MessageReader<WaveCleared>, mut stats: ResMut<RunStats>) {
+    for message in cleared_messages.read() {
+        if message.skipped {
+            continue;
+        }
+        if message.timed_out {
+            stats.waves_overrun += 1;
+        } else {
+            stats.waves_cleared += 1;
+        }
+    }
+}
+
+type NewPlayerShot = (Added<Projectile>, With<PlayerOwned>);
+
+/// Every player projectile spawned counts as a shot (a shotgun blast is several). A held
+/// beam only counts the steps that carry damage
+fn record_shots_fired(
+    projectiles: Query<(&SourceWeapon, Option<&Beam>), NewPlayerShot>,
+    mut stats: ResMut<RunStats>,
+) {
+    for (source, beam) in &projectiles {
+        if beam.is_some_and(|beam| beam.damage == 0) {
+            continue;
+        }
+        stats.shots.entry(source.0).or_default().fired += 1;
+    }
+}
+
+/// Only what got through, hits soaked up by i-frames or god mode don't count
+fn record_damage_taken(
+    mut hits: MessageReader<HitTaken>,
+    player: Query<(), With<Player>>,
+    mut stats: ResMut<RunStats>,
+) {
+    for hit in hits.read() {
+        if player.contains(hit.target) {
+            stats.damage_taken += hit.amount;
+        }
+    }
+}
+
+/// Runs between apply_damage writing the DeathEvents and apply_death despawning anything
+fn record_deaths(
+    mut death_messages: MessageReader<DeathEvent>,
+    drones: Query<&DroneType>,
+    structures: Query<(), With<Structure>>,
+    mut stats: ResMut<RunStats>,
+) {
+    for event in death_messages.read() {
+        if let Ok(drone) = drones.get(event.entity) {
+            *stats.kills.entry(drone.drone_type).or_insert(0) += 1;
+        }
+        if structures.contains(event.entity) {
+            stats.structures_lost += 1;
+        }
+    }
+}
+
+fn record_structures_built(
+    structures: Query<(), Added<Structure>>,
+    mut stats: ResMut<RunStats>,
+) {
+    stats.structures_built += structures.iter().count() as u32;
+}
+
+pub struct RunStatsPlugin;
+
+impl Plugin for RunStatsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<RunStats>()
+            .add_systems(Update, (
+                tick_time_survived,
+                record_shots_fired,
+                record_structures_built,
+            ).run_if(in_state(GameState::Playing)))
+            .add_systems(FixedUpdate, (
+                record_damage_taken.after(apply_damage),
+                record_deaths.after(apply_damage).before(apply_death),
+            ).in_set(SimulationSet::Damage).run_if(in_state(GameState::Playing)))
+            // The last wave clearing switches straight to Victory, count it before the end screen shows
+            .add_systems(FixedUpdate, record_waves.after(check_wave_clear).in_set(SimulationSet::Damage));
+    }
+}
@@ -2,8 +2,10 @@ pub mod drone_config;
 pub mod drone_spawner;
 pub mod resource_spawner;
 pub mod wave;
+pub mod wave_director;
 
 pub use drone_spawner::*;
 pub use drone_config::*;
 pub use resource_spawner::*;
-pub use wave::*;
\ No newline at end of file
+pub use wave::*;
+pub use wave_director::*;
\ No newline at end of file
@@ -1,5 +1,5 @@
 use bevy::prelude::*;
-use crate::combat::ProjectileConfig;
+use crate::combat::{FirePattern, ProjectileConfig};
 
 #[derive(Clone, Copy, Hash, PartialEq, Eq)]
 pub enum DroneKind{
@@ -7,6 +7,17 @@ pub enum DroneKind{
     Shooter,
 }
 
+impl DroneKind {
+    pub const ALL: [DroneKind; 2] = [DroneKind::Chaser, DroneKind::Shooter];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            DroneKind::Chaser => "Chaser",
+            DroneKind::Shooter => "Shooter",
+        }
+    }
+}
+
 #[derive(Component)]
 pub struct DroneType{
     pub drone_type: DroneKind,
@@ -22,7 +33,6 @@ pub struct DroneConfig {
     pub health: u32,
     pub health_bar_width: f32,
     pub health_bar_offset: f32,
-    pub hitbox_radius: f32,
     pub speed: f32,
     pub behaviors: Vec<BehaviorConfig>,
     pub movement: MovementConfig,
@@ -34,7 +44,7 @@ pub struct DroneConfig {
 pub enum BehaviorConfig {
     CollideTarget,
     MaintainRange { range: f32 },
-    ShootAtTarget { cooldown_secs: f32, projectile: ProjectileConfig },
+    ShootAtTarget { cooldown_secs: f32, projectile: ProjectileConfig, pattern: FirePattern },
     ExplodeOnContact { damage: u32 },
 }
 
@@ -46,6 +56,13 @@ pub enum MovementConfig {
 
 // Preset drone configurations
 impl DroneConfig {
+    pub fn from_kind(kind: DroneKind) -> Self {
+        match kind {
+            DroneKind::Chaser => Self::chaser(),
+            DroneKind::Shooter => Self::shooter(),
+        }
+    }
+
     /// Basic chaser - runs at player and explodes
     pub fn chaser() -> Self {
         Self {
@@ -53,7 +70,6 @@ impl DroneConfig {
             glyph: "<{=}>".to_string(),
             color: Color::srgb(1.0, 0.0, 0.0),
             health: 10,
-            hitbox_radius: 30.0,
             speed: 150.0,
             font_size: 24.0,
             movement: MovementConfig::Direct,
@@ -75,7 +91,6 @@ impl DroneConfig {
             color: Color::srgb(1.0, 0.5, 0.0),
             health: 10,
             font_size: 24.0,
-            hitbox_radius: 30.0,
             speed: 90.0,
             movement: MovementConfig::Direct,
             behaviors: vec![
@@ -83,6 +98,7 @@ impl DroneConfig {
                 BehaviorConfig::ShootAtTarget {
                     cooldown_secs: 0.5,
                     projectile: ProjectileConfig::enemy_bullet(),
+                    pattern: FirePattern::Single,
                 },
             ],
             health_bar_width: 32.0,
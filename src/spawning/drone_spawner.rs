@@ -2,36 +2,42 @@ use bevy::prelude::*;
 use crate::{
     ascii_sprite::AsciiSprite,
     combat::{Health, HealthBar},
+    difficulty::Difficulty,
     enemy::Enemy,
     npc_behaviors::{CollideTarget, ExplodeOnContact, MaintainRangeFromTarget, ShootAtTarget},
-    physics::{CircleHitBox, DesiredDirection, DirectMovement, Velocity},
+    physics::{Collider, DesiredDirection, DirectMovement, Velocity},
     spawning::{DroneType, MovementConfig}
 };
 use super::{DroneConfig, BehaviorConfig};
 
-//turn config into entity
+//turn config into entity, scaled by the run's difficulty
 pub fn spawn_drone(
     commands: &mut Commands,
     config: &DroneConfig,
+    difficulty: &Difficulty,
     pos: Vec2,
     target: Entity
 ) -> Entity {
     //make entity of drone 
     //Loop through behavior configs to attach additional components
     //spawn
+    let sprite = AsciiSprite{
+        glyph: config.glyph.to_string(), 
+        color: config.color, 
+        font_size: config.font_size,
+        bg_color: None //todo: implement this if i want to later
+    };
+    //hitbox fits the glyph, so wide drones get a capsule instead of a big circle
+    let hitbox = Collider::fit_ascii(&sprite);
+
     let mut entity = commands.spawn((
         Transform::from_translation(pos.extend(0.0)), //Transform expects Vec3, we have Vec2
-        AsciiSprite{
-            glyph: config.glyph.to_string(), 
-            color: config.color, 
-            font_size: config.font_size,
-            bg_color: None //todo: implement this if i want to later
-        },
+        sprite,
         Enemy, //TODO: hard codded enemy probalby not good
-        CircleHitBox { radius: config.hitbox_radius}, //TODO: HARDCODED BAD BAD WHAT IF OTHER TYPE OF HITBOX
-        Velocity{speed: config.speed, direction: Vec2::ZERO},
+        hitbox,
+        Velocity{speed: config.speed * difficulty.drone_speed, direction: Vec2::ZERO},
         DesiredDirection::default(),
-        Health::new(config.health),
+        Health::new(difficulty.scale_drone_health(config.health)),
         HealthBar {max_width: config.health_bar_width, offset: config.health_bar_offset},
         DroneType{drone_type: config.drone_type}
     ));
@@ -54,11 +60,12 @@ pub fn spawn_drone(
             BehaviorConfig::MaintainRange { range } => {
                 entity.insert(MaintainRangeFromTarget { target, range: *range });
             }
-            BehaviorConfig::ShootAtTarget { cooldown_secs, projectile } => {
+            BehaviorConfig::ShootAtTarget { cooldown_secs, projectile, pattern } => {
                 entity.insert(ShootAtTarget {
                     target,
                     cooldown: Timer::from_seconds(*cooldown_secs, TimerMode::Repeating),
                     config: projectile.clone(),
+                    pattern: *pattern,
                 });
             }
             BehaviorConfig::ExplodeOnContact { damage: _ } => {
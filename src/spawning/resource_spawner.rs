@@ -1,6 +1,8 @@
 use bevy::prelude::*;
 use rand::RngExt;
 use crate::ascii_sprite::AsciiSprite;
+use crate::combat::HealthPickup;
+use crate::game_rng::GameRng;
 use crate::physics::{DesiredDirection, MagneticAttraction, MagnetizedTo, Velocity};
 use crate::resources::{ResourceDrop, ResourceLifeTimer, ResourceType};
 
@@ -9,16 +11,16 @@ const SPREAD_RADIUS: f32 = 20.0;
 const MAGNETIC_RANGE: f32 = 200.0;
 const MAGNETIC_PEAK_SPEED: f32 = 100.0;  
 const MAGNETIC_LERP_FACTOR: f32 = 0.1; 
+const HEALTH_PICKUP_AMOUNT: u32 = 2;
 
 /// Spawns a resource drop at the given position with a random offset
 pub fn spawn_resource(
     commands: &mut Commands,
+    rng: &mut GameRng,
     resource_type: ResourceType,
     base_pos: Vec2,
     player: Entity,
 ) {
-    let mut rng = rand::rng();
-
     // Random offset so items don't stack
     let offset = Vec2::new(
         rng.random_range(-SPREAD_RADIUS..SPREAD_RADIUS),
@@ -44,15 +46,37 @@ pub fn spawn_resource(
     ));
 }
 
+/// Spawns a health pickup near `base_pos`. Not magnetic, it has to be walked over
+pub fn spawn_health_pickup(commands: &mut Commands, rng: &mut GameRng, base_pos: Vec2) {
+    let offset = Vec2::new(
+        rng.random_range(-SPREAD_RADIUS..SPREAD_RADIUS),
+        rng.random_range(-SPREAD_RADIUS..SPREAD_RADIUS),
+    );
+
+    commands.spawn((
+        Transform::from_translation((base_pos + offset).extend(0.0)),
+        Visibility::Inherited,
+        AsciiSprite {
+            glyph: "+".to_string(),
+            color: Color::srgb(1.0, 0.2, 0.3),
+            font_size: 24.0,
+            bg_color: None,
+        },
+        HealthPickup { amount: HEALTH_PICKUP_AMOUNT },
+        ResourceLifeTimer(Timer::from_seconds(RESOURCE_LIFETIME_SECS, TimerMode::Once)),
+    ));
+}
+
 /// Spawns multiple resources of the same type
 pub fn spawn_resources(
     commands: &mut Commands,
+    rng: &mut GameRng,
     resource_type: ResourceType,
     base_pos: Vec2,
     count: u32,
     player: Entity,
 ) {
     for _ in 0..count {
-        spawn_resource(commands, resource_type, base_pos, player);
+        spawn_resource(commands, rng, resource_type, base_pos, player);
     }
 }
@@ -1,8 +1,10 @@
 use bevy::prelude::*;
-use crate::{enemy::Enemy, state::GameState};
+use bevy::ecs::system::SystemParam;
+use bevy::time::Stopwatch;
+use crate::{difficulty::Difficulty, enemy::Enemy, state::GameState};
 use crate::player::Player;
 use crate::state::WavePhase;
-use super::{spawn_drone, DroneConfig};
+use super::{spawn_drone, ring_position, DroneConfig, WaveDirector, WaveEvent, WaveEventKind};
 
 #[derive(Resource)]
 pub struct WaveState {
@@ -10,6 +12,17 @@ pub struct WaveState {
     pub spawn_index: usize,
     pub spawn_timer: Timer,
     pub countdown_timer: Timer,
+    /// Time since the current wave started spawning (drives WaveEvents)
+    pub wave_clock: Stopwatch,
+    /// Index of the next WaveEvent to fire for the current wave
+    pub next_event: usize,
+    /// Once the wave is fully spawned, the next wave starts when this runs out (None = no pressure)
+    pub pressure_timer: Option<Timer>,
+    /// Drones called in by Reinforcements events, waiting for room under the live cap
+    pub reinforcements: Vec<DroneConfig>,
+    pub reinforcement_timer: Timer,
+    /// Set by the console's skipwave, the wave ends without counting as cleared
+    pub skipped: bool,
 }
 
 impl Default for WaveState {
@@ -19,10 +32,26 @@ impl Default for WaveState {
             spawn_index: 0,
             spawn_timer: Timer::from_seconds(0.3, TimerMode::Repeating),
             countdown_timer: Timer::from_seconds(2.0, TimerMode::Once),
+            wave_clock: Stopwatch::new(),
+            next_event: 0,
+            pressure_timer: None,
+            reinforcements: Vec::new(),
+            reinforcement_timer: Timer::from_seconds(0.5, TimerMode::Repeating),
+            skipped: false,
         }
     }
 }
 
+/// Written when a wave ends, either cleared or rolled over by the pressure timer
+#[derive(Message)]
+pub struct WaveCleared {
+    /// 1-based number of the wave that just ended
+    pub wave_number: usize,
+    pub timed_out: bool,
+    /// Ended with the console's skipwave, left out of the run stats
+    pub skipped: bool,
+}
+
 #[derive(Resource)]
 pub struct WaveDefinitions {
     pub waves: Vec<WaveDefinition>,
@@ -30,6 +59,10 @@ pub struct WaveDefinitions {
 
 pub struct WaveDefinition {
     pub drones: Vec<DroneConfig>,
+    /// Special events fired during the wave, sorted by at_secs
+    pub events: Vec<WaveEvent>,
+    /// Overrides WaveDirector::default_pressure_secs for this wave, None uses the default
+    pub pressure_secs: Option<f32>,
 }
 
 impl Default for WaveDefinitions {
@@ -43,6 +76,8 @@ impl Default for WaveDefinitions {
                         DroneConfig::chaser(),
                         DroneConfig::chaser(),
                     ],
+                    events: vec![],
+                    pressure_secs: None,
                 },
                 // Wave 2: 2 chasers + 2 shooters
                 WaveDefinition {
@@ -52,6 +87,15 @@ impl Default for WaveDefinitions {
                         DroneConfig::shooter(),
                         DroneConfig::shooter(),
                     ],
+                    events: vec![
+                        WaveEvent {
+                            at_secs: 10.0,
+                            kind: WaveEventKind::Reinforcements {
+                                drones: vec![DroneConfig::chaser(), DroneConfig::chaser()],
+                            },
+                        },
+                    ],
+                    pressure_secs: None,
                 },
                 // Wave 3: 3 chasers + 5 shooters
                 WaveDefinition {
@@ -65,12 +109,32 @@ impl Default for WaveDefinitions {
                         DroneConfig::shooter(),
                         DroneConfig::shooter(),
                     ],
+                    events: vec![
+                        WaveEvent {
+                            at_secs: 8.0,
+                            kind: WaveEventKind::SwarmBurst { drone: DroneConfig::chaser(), count: 6 },
+                        },
+                        WaveEvent {
+                            at_secs: 20.0,
+                            kind: WaveEventKind::Reinforcements {
+                                drones: vec![DroneConfig::shooter(), DroneConfig::shooter()],
+                            },
+                        },
+                    ],
+                    // Uses the default pressure, but the last wave never times out (is_last_wave in check_wave_clear)
+                    pressure_secs: None,
                 },
             ],
         }
     }
 }
 
+/// Restart the countdown whenever we enter it (also covers continuing a suspended run,
+/// where WavePhase comes back at its default Countdown)
+pub fn reset_countdown(mut wave_state: ResMut<WaveState>) {
+    wave_state.countdown_timer.reset();
+}
+
 /// Countdown before wave starts
 pub fn countdown_system(
     time: Res<Time>,
@@ -85,18 +149,25 @@ pub fn countdown_system(
     }
 }
 
-/// Spawn drones one at a time with a short delay
+/// What spawn_system reads to decide which drone goes where
+#[derive(SystemParam)]
+pub struct WaveSpawnSettings<'w> {
+    wave_defs: Res<'w, WaveDefinitions>,
+    director: Res<'w, WaveDirector>,
+    difficulty: Res<'w, Difficulty>,
+}
+
+/// Spawn drones one at a time with a short delay (paused by the director's live drone cap)
 pub fn spawn_system(
     mut commands: Commands,
     time: Res<Time>,
     mut wave_state: ResMut<WaveState>,
-    wave_defs: Res<WaveDefinitions>,
-    player_query: Query<Entity, With<Player>>,
+    settings: WaveSpawnSettings,
+    player: Single<Entity, With<Player>>,
     mut next_phase: ResMut<NextState<WavePhase>>,
 ) {
-    let Ok(player_entity) = player_query.single() else {
-        return;
-    };
+    let WaveSpawnSettings { wave_defs, director, difficulty } = settings;
+    let player_entity = *player;
 
     let Some(current_wave) = wave_defs.waves.get(wave_state.wave_number) else {
         // No more waves
@@ -110,12 +181,15 @@ pub fn spawn_system(
         if wave_state.spawn_index < current_wave.drones.len() {
             let config = &current_wave.drones[wave_state.spawn_index];
 
-            // Spawn at random position around player
-            let angle = wave_state.spawn_index as f32 * std::f32::consts::TAU / current_wave.drones.len() as f32;
-            let spawn_distance = 400.0;
-            let spawn_pos = Vec2::new(angle.cos() * spawn_distance, angle.sin() * spawn_distance);
+            // Spread the wave evenly on a ring around the arena center
+            let spawn_pos = ring_position(
+                Vec2::ZERO,
+                wave_state.spawn_index,
+                current_wave.drones.len(),
+                director.spawn_distance,
+            );
 
-            spawn_drone(&mut commands, config, spawn_pos, player_entity);
+            spawn_drone(&mut commands, config, &difficulty, spawn_pos, player_entity);
 
             info!("Spawned {} ({}/{})", config.name, wave_state.spawn_index + 1, current_wave.drones.len());
             wave_state.spawn_index += 1;
@@ -127,20 +201,36 @@ pub fn spawn_system(
     }
 }
 
-/// Check if all enemies are dead
+/// Check if all enemies are dead, or if the wave director's pressure timer ran out.
+/// The last wave always has to be cleared.
 pub fn check_wave_clear(
     enemy_query: Query<Entity, With<Enemy>>,
     mut wave_state: ResMut<WaveState>,
     wave_defs: Res<WaveDefinitions>,
     mut next_phase: ResMut<NextState<WavePhase>>,
     mut next_state: ResMut<NextState<GameState>>,
+    mut cleared_messages: MessageWriter<WaveCleared>,
 ) {
-    if enemy_query.iter().count() == 0 {
-        info!("Wave {} cleared!", wave_state.wave_number + 1);
+    let is_last_wave = wave_state.wave_number + 1 >= wave_defs.waves.len();
+    let pressure_expired = !is_last_wave
+        && wave_state.pressure_timer.as_ref().is_some_and(|timer| timer.is_finished());
+    let cleared = enemy_query.iter().count() == 0 && wave_state.reinforcements.is_empty();
+
+    if cleared || pressure_expired {
+        if cleared {
+            info!("Wave {} cleared!", wave_state.wave_number + 1);
+        } else {
+            info!("Wave {} timed out with {} drones left, next wave incoming!", wave_state.wave_number + 1, enemy_query.iter().count());
+        }
+        cleared_messages.write(WaveCleared {
+            wave_number: wave_state.wave_number + 1,
+            timed_out: !cleared,
+            skipped: wave_state.skipped,
+        });
+        wave_state.skipped = false;
 
         wave_state.wave_number += 1;
         wave_state.spawn_index = 0;
-        wave_state.countdown_timer.reset();
 
         if wave_state.wave_number >= wave_defs.waves.len() {
             info!("All waves complete! Victory!");
@@ -0,0 +1,164 @@
+// The wave director sits on top of the WavePhase loop. It keeps the pressure on:
+// waves can roll over on a timer even if drones are still alive, the number of
+// live drones is capped, and waves can schedule special events (swarms, reinforcements)
+use bevy::prelude::*;
+use crate::difficulty::Difficulty;
+use crate::enemy::Enemy;
+use crate::player::Player;
+use super::{spawn_drone, DroneConfig, WaveDefinitions, WaveState};
+
+/// Global knobs for the wave director
+#[derive(Resource)]
+pub struct WaveDirector {
+    /// Spawning pauses while this many drones are alive (swarm bursts ignore it)
+    pub max_live_drones: usize,
+    /// Seconds a wave has to be cleared once fully spawned before the next one starts anyway.
+    /// Used when a WaveDefinition doesn't set its own pressure_secs. None = wait forever
+    pub default_pressure_secs: Option<f32>,
+    pub spawn_distance: f32,
+}
+
+impl Default for WaveDirector {
+    fn default() -> Self {
+        Self {
+            max_live_drones: 12,
+            default_pressure_secs: Some(45.0),
+            spawn_distance: 400.0,
+        }
+    }
+}
+
+/// A special event scheduled inside a wave
+#[derive(Clone)]
+pub struct WaveEvent {
+    /// Seconds after the wave starts spawning
+    pub at_secs: f32,
+    pub kind: WaveEventKind,
+}
+
+#[derive(Clone)]
+pub enum WaveEventKind {
+    /// Spawns `count` drones at once in a ring around the player, ignoring the live cap
+    SwarmBurst { drone: DroneConfig, count: usize },
+    /// Queues extra drones that come in from one side, respecting the live cap
+    Reinforcements { drones: Vec<DroneConfig> },
+}
+
+/// Position on a ring of `total` evenly spaced points around `center`
+pub fn ring_position(center: Vec2, index: usize, total: usize, distance: f32) -> Vec2 {
+    let angle = index as f32 * std::f32::consts::TAU / total.max(1) as f32;
+    center + Vec2::new(angle.cos() * distance, angle.sin() * distance)
+}
+
+/// Run condition: true while fewer than max_live_drones drones are alive
+pub fn under_live_drone_cap(
+    director: Res<WaveDirector>,
+    enemy_query: Query<(), With<Enemy>>,
+) -> bool {
+    enemy_query.iter().count() < director.max_live_drones
+}
+
+/// Starts the wave clock and pressure timer for the wave about to spawn
+pub fn start_wave_clock(
+    mut wave_state: ResMut<WaveState>,
+    wave_defs: Res<WaveDefinitions>,
+    director: Res<WaveDirector>,
+) {
+    // Resuming a wave that was already spawning (continued from the main menu), keep its clock
+    if wave_state.spawn_index > 0 {
+        return;
+    }
+
+    let pressure_secs = wave_defs.waves
+        .get(wave_state.wave_number)
+        .and_then(|wave| wave.pressure_secs)
+        .or(director.default_pressure_secs);
+
+    wave_state.wave_clock.reset();
+    wave_state.next_event = 0;
+    wave_state.pressure_timer = pressure_secs.map(|secs| Timer::from_seconds(secs, TimerMode::Once));
+}
+
+/// Counts down the pressure timer once the wave is fully spawned (check_wave_clear reads it)
+pub fn tick_wave_pressure(
+    time: Res<Time>,
+    mut wave_state: ResMut<WaveState>,
+) {
+    if let Some(timer) = wave_state.pressure_timer.as_mut() {
+        timer.tick(time.delta());
+    }
+}
+
+/// Fires scheduled wave events once the wave clock passes their time
+pub fn wave_event_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut wave_state: ResMut<WaveState>,
+    wave_defs: Res<WaveDefinitions>,
+    director: Res<WaveDirector>,
+    difficulty: Res<Difficulty>,
+    player_query: Query<(Entity, &Transform), With<Player>>,
+) {
+    let Ok((player_entity, player_transform)) = player_query.single() else {
+        return;
+    };
+    let Some(current_wave) = wave_defs.waves.get(wave_state.wave_number) else {
+        return;
+    };
+
+    wave_state.wave_clock.tick(time.delta());
+    let elapsed = wave_state.wave_clock.elapsed_secs();
+    let player_pos = player_transform.translation.truncate();
+
+    while let Some(event) = current_wave.events.get(wave_state.next_event) {
+        if event.at_secs > elapsed {
+            break;
+        }
+        wave_state.next_event += 1;
+
+        match &event.kind {
+            WaveEventKind::SwarmBurst { drone, count } => {
+                info!("Swarm burst! {} {}s incoming", count, drone.name);
+                for i in 0..*count {
+                    let pos = ring_position(player_pos, i, *count, director.spawn_distance);
+                    spawn_drone(&mut commands, drone, &difficulty, pos, player_entity);
+                }
+            }
+            WaveEventKind::Reinforcements { drones } => {
+                info!("Reinforcements called in: {} drones", drones.len());
+                wave_state.reinforcements.extend(drones.iter().cloned());
+            }
+        }
+    }
+}
+
+/// Drains queued reinforcements, all coming in from the same side
+pub fn spawn_reinforcements(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut wave_state: ResMut<WaveState>,
+    director: Res<WaveDirector>,
+    difficulty: Res<Difficulty>,
+    player_query: Query<(Entity, &Transform), With<Player>>,
+) {
+    if wave_state.reinforcements.is_empty() {
+        return;
+    }
+    let Ok((player_entity, player_transform)) = player_query.single() else {
+        return;
+    };
+
+    wave_state.reinforcement_timer.tick(time.delta());
+    if !wave_state.reinforcement_timer.just_finished() {
+        return;
+    }
+
+    let config = wave_state.reinforcements.remove(0);
+    // Every reinforcement in a wave comes from the same side, staggered a bit further out
+    let side = (wave_state.wave_number * 3) % 8;
+    let jitter = wave_state.reinforcements.len() as f32 * 20.0;
+    let pos = ring_position(player_transform.translation.truncate(), side, 8, director.spawn_distance + jitter);
+
+    spawn_drone(&mut commands, &config, &difficulty, pos, player_entity);
+    info!("Reinforcement {} arrived ({} left)", config.name, wave_state.reinforcements.len());
+}
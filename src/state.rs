@@ -4,20 +4,33 @@ mod playing;
 mod game_over;
 mod victory;
 mod paused;
+mod main_menu;
+mod run_setup;
+mod run_summary;
+mod run_history_view;
 
 use crate::crafting;
 
 use bevy::prelude::*;
 use crate::game_fonts;
+use crate::combat::apply_death;
+use crate::physics::SimulationSet;
+use crate::run_history::record_run;
 use crate::spawning::{
     countdown_system, spawn_system, check_wave_clear,
-    WaveState, WaveDefinitions,
+    start_wave_clock, wave_event_system, spawn_reinforcements, under_live_drone_cap,
+    tick_wave_pressure, reset_countdown,
+    WaveState, WaveDefinitions, WaveDirector, WaveCleared,
 };
 
 pub use game_over::toggle_restart;
+pub use game_over::GameOverReason;
 pub use game_state::GameState;
 pub use game_state::PauseScreen;
+pub use game_state::MenuScreen;
+pub use run_setup::{RunSetup, RunStarted, StartingLoadout, start_run};
 pub use game_state::WavePhase;
+pub use game_state::InGame;
 
 #[derive(Resource)]
 struct LoadingTimer(Timer);
@@ -28,6 +41,10 @@ impl Plugin for StatePlugin {
     fn build(&self, app: &mut App) {
         app
         .insert_resource(LoadingTimer(Timer::from_seconds(6.0, TimerMode::Once)))
+        .init_resource::<GameOverReason>()
+        .init_resource::<RunSetup>()
+        .add_message::<RunStarted>()
+        .add_systems(Startup, run_setup::apply_cli_seed)
         .init_state::<GameState>()
         
         // Loading state systems
@@ -40,6 +57,30 @@ impl Plugin for StatePlugin {
             loading::despawn_loading_screen,
         ))
 
+        // MenuScreen SubState (tracks which screen of the main menu is showing)
+        .add_sub_state::<MenuScreen>()
+
+        // Main menu (MenuScreen::Main)
+        .add_systems(OnEnter(MenuScreen::Main), main_menu::spawn_main_menu)
+        .add_systems(OnExit(MenuScreen::Main), main_menu::despawn_main_menu)
+        .add_systems(Update, main_menu::handle_main_menu_buttons.run_if(in_state(MenuScreen::Main)))
+
+        // Run setup (MenuScreen::RunSetup)
+        .add_systems(OnEnter(MenuScreen::RunSetup), main_menu::spawn_run_setup_menu)
+        .add_systems(OnExit(MenuScreen::RunSetup), main_menu::despawn_run_setup_menu)
+        .add_systems(Update, (
+            main_menu::handle_run_setup_buttons,
+            main_menu::handle_run_setup_cycle_buttons,
+        ).run_if(in_state(MenuScreen::RunSetup)))
+
+        // Main menu settings (MenuScreen::Settings), reuses the pause menu volume controls
+        .add_systems(OnEnter(MenuScreen::Settings), main_menu::spawn_menu_settings)
+        .add_systems(OnExit(MenuScreen::Settings), main_menu::despawn_menu_settings)
+        .add_systems(Update, (
+            main_menu::handle_menu_settings_buttons,
+            paused::handle_volume_buttons,
+        ).run_if(in_state(MenuScreen::Settings)))
+
         // PauseScreen SubState (tracks which screen while paused)
         .add_sub_state::<PauseScreen>()
 
@@ -69,30 +110,33 @@ impl Plugin for StatePlugin {
         .add_systems(Update, (paused::handle_audio_buttons.run_if(in_state(PauseScreen::Audio)), paused::handle_volume_buttons.run_if(in_state(PauseScreen::Audio))))
 
         //playing state systems
-        .add_systems(OnEnter(GameState::Playing), (playing::spawn_player, crate::inventory::spawn_hotbar, crate::inventory::spawn_weapon_hotbar))
-        .add_systems(OnExit(GameState::Playing), (crate::inventory::despawn_hotbar, crate::inventory::despawn_weapon_hotbar))
+        .add_systems(OnEnter(GameState::Playing), (playing::spawn_player, crate::inventory::spawn_hotbar, crate::inventory::spawn_weapon_hotbar, crate::inventory::spawn_consumable_hotbar, crate::inventory::spawn_weapon_pickup_prompt))
+        .add_systems(OnExit(GameState::Playing), (crate::inventory::despawn_hotbar, crate::inventory::despawn_weapon_hotbar, crate::inventory::despawn_consumable_hotbar, crate::inventory::despawn_weapon_pickup_prompt))
         .add_systems(Update, crate::inventory::update_hotbar.run_if(in_state(GameState::Playing)))
-        .add_systems(Update, crate::inventory::update_weapon_hotbar.run_if(in_state(GameState::Playing)))
+        .add_systems(Update, (crate::inventory::update_consumable_hotbar, crate::inventory::update_consumable_hotbar_status).run_if(in_state(GameState::Playing)))
+        .add_systems(Update, (crate::inventory::update_weapon_hotbar, crate::inventory::update_weapon_hotbar_ammo, crate::inventory::update_weapon_hotbar_tooltip, crate::inventory::update_weapon_pickup_prompt).run_if(in_state(GameState::Playing)))
         .add_systems(Update, toggle_pause)
         .add_systems(Update, crafting::toggle_crafting.run_if(in_state(GameState::Playing)))
 
         //Game Over systems
         .add_systems(OnEnter(GameState::GameOver), (
             game_over::cleanup_game_entities,
-            game_over::spawn_game_over_menu,
+            game_over::spawn_game_over_menu.after(record_run),
             game_over::play_game_over_music,
+            record_run,
         ))
-        .add_systems(OnExit(GameState::GameOver), game_over::despawn_game_over_menu)
-        .add_systems(Update, toggle_restart.run_if(in_state(GameState::GameOver)))
+        .add_systems(OnExit(GameState::GameOver), (game_over::despawn_game_over_menu, run_history_view::despawn_run_history_view))
+        .add_systems(Update, (toggle_restart, run_history_view::toggle_run_history_view).run_if(in_state(GameState::GameOver)))
 
         //Victory state systems
         .add_systems(OnEnter(GameState::Victory), (
             game_over::cleanup_game_entities,//reusing this from game over
-            victory::spawn_victory_menu,
+            victory::spawn_victory_menu.after(record_run),
             victory::play_victory_music,
+            record_run,
         ))
-        .add_systems(OnExit(GameState::Victory), victory::despawn_victory_menu)
-        .add_systems(Update, toggle_restart.run_if(in_state(GameState::Victory)))
+        .add_systems(OnExit(GameState::Victory), (victory::despawn_victory_menu, run_history_view::despawn_run_history_view))
+        .add_systems(Update, (toggle_restart, run_history_view::toggle_run_history_view).run_if(in_state(GameState::Victory)))
 
         // InGame computed state (active during Playing OR Paused)
         // Must be registered before WavePhase since WavePhase depends on it
@@ -102,9 +146,32 @@ impl Plugin for StatePlugin {
         .add_sub_state::<WavePhase>()
         .init_resource::<WaveState>()
         .init_resource::<WaveDefinitions>()
-        .add_systems(Update, countdown_system.run_if(in_state(WavePhase::Countdown)))
-        .add_systems(Update, spawn_system.run_if(in_state(WavePhase::Spawning)))
-        .add_systems(Update, check_wave_clear.run_if(in_state(WavePhase::InProgress)));
+        .init_resource::<WaveDirector>()
+        .add_message::<WaveCleared>()
+        .add_systems(OnEnter(WavePhase::Countdown), reset_countdown)
+        .add_systems(FixedUpdate, (
+            countdown_system.run_if(in_state(WavePhase::Countdown)),
+            spawn_system.run_if(in_state(WavePhase::Spawning)).run_if(under_live_drone_cap),
+        ).in_set(SimulationSet::Intent))
+        // Counts what's left after this step's deaths are despawned
+        .add_systems(FixedUpdate, check_wave_clear
+            .after(apply_death)
+            .in_set(SimulationSet::Damage)
+            .run_if(in_state(WavePhase::InProgress)))
+
+        // Wave director: timed pressure, special events and reinforcements
+        .add_systems(OnEnter(WavePhase::Spawning), start_wave_clock)
+        .add_systems(FixedUpdate, (
+            wave_event_system,
+            spawn_reinforcements.run_if(under_live_drone_cap),
+        ).in_set(SimulationSet::Intent)
+         .run_if(in_state(GameState::Playing))
+         .run_if(in_state(WavePhase::Spawning).or(in_state(WavePhase::InProgress))))
+        .add_systems(FixedUpdate, tick_wave_pressure
+            .before(check_wave_clear)
+            .in_set(SimulationSet::Damage)
+            .run_if(in_state(GameState::Playing))
+            .run_if(in_state(WavePhase::InProgress)));
     }
 }
 
@@ -2,22 +2,46 @@ use bevy::prelude::*;
 
 use crate::audio::{AudioSettings, MusicTrack, play_music, stop_music};
 use crate::building::{BuildGrid, BuildMode, Structure, GridOverlay, GhostPreview};
-use crate::combat::Projectile;
+use crate::combat::{Grenade, HealthPickup, Projectile};
 use crate::enemy::Enemy;
 use crate::player::Player;
 use crate::resources::ResourceDrop;
+use crate::inventory::WeaponPickup;
+use crate::combat::WeaponDefinitions;
+use crate::run_stats::RunStats;
 use crate::spawning::WaveState;
 use crate::state::GameState;
+use crate::game_rng::GameRng;
+use crate::run_history::RunHistory;
+use super::run_summary::{spawn_end_footer, spawn_run_summary};
 
 #[derive(Component)]
 pub struct GameOverMenu;
 
-pub fn spawn_game_over_menu(mut commands: Commands) {
+/// Why the last run ended in GameOver, set by whoever triggers it
+#[derive(Resource, Default, Clone, Copy, PartialEq, Eq)]
+pub enum GameOverReason {
+    #[default]
+    PlayerKilled,
+    BeaconDestroyed,
+}
+
+impl GameOverReason {
+    pub fn message(&self) -> &'static str {
+        match self {
+            GameOverReason::PlayerKilled => "You were destroyed",
+            GameOverReason::BeaconDestroyed => "The extraction beacon was destroyed",
+        }
+    }
+}
+
+pub fn spawn_game_over_menu(mut commands: Commands, reason: Res<GameOverReason>, stats: Res<RunStats>, weapons: Res<WeaponDefinitions>, history: Res<RunHistory>, rng: Res<GameRng>) {
     commands.spawn((
         GameOverMenu,
         Node {
             width: Val::Percent(100.0),
             height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
             justify_content: JustifyContent::Center,
             align_items: AlignItems::Center,
             ..default()
@@ -25,7 +49,7 @@ pub fn spawn_game_over_menu(mut commands: Commands) {
         BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
     )).with_children(|parent| {
         parent.spawn((
-            Text::new("Game Over\n\nPress ESC to restart"),
+            Text::new(format!("Game Over\n{}", reason.message())),
             TextFont {
                 font_size: 36.0,
                 ..default()
@@ -33,6 +57,10 @@ pub fn spawn_game_over_menu(mut commands: Commands) {
             TextColor(Color::WHITE),
             TextLayout::new_with_justify(Justify::Center),
         ));
+
+        spawn_run_summary(parent, &stats, &weapons, rng.seed());
+
+        spawn_end_footer(parent, &history);
     });
     
     info!("Defeat menu spawned");
@@ -63,38 +91,28 @@ pub fn despawn_game_over_menu(
     info!("Defeat menu despawned");
 }
 
+/// Everything that only exists during a run
+type RunEntity = Or<(
+    With<Player>,
+    With<Enemy>,
+    With<Projectile>,
+    With<Grenade>,
+    With<ResourceDrop>,
+    With<WeaponPickup>,
+    With<HealthPickup>,
+    With<Structure>,
+    With<GridOverlay>,
+    With<GhostPreview>,
+)>;
+
 pub fn cleanup_game_entities(
     mut commands: Commands,
-    players: Query<Entity, With<Player>>,
-    enemies: Query<Entity, With<Enemy>>,
-    projectiles: Query<Entity, With<Projectile>>,
-    resources: Query<Entity, With<ResourceDrop>>,
-    structures: Query<Entity, With<Structure>>,
-    overlays: Query<Entity, With<GridOverlay>>,
-    ghosts: Query<Entity, With<GhostPreview>>,
+    run_entities: Query<Entity, RunEntity>,
     mut wave_state: ResMut<WaveState>,
     mut build_grid: ResMut<BuildGrid>,
     mut build_mode: ResMut<BuildMode>,
 ) {
-    for entity in players.iter() {
-        commands.entity(entity).despawn();
-    }
-    for entity in enemies.iter() {
-        commands.entity(entity).despawn();
-    }
-    for entity in projectiles.iter() {
-        commands.entity(entity).despawn();
-    }
-    for entity in resources.iter() {
-        commands.entity(entity).despawn();
-    }
-    for entity in structures.iter() {
-        commands.entity(entity).despawn();
-    }
-    for entity in overlays.iter() {
-        commands.entity(entity).despawn();
-    }
-    for entity in ghosts.iter() {
+    for entity in run_entities.iter() {
         commands.entity(entity).despawn();
     }
 
@@ -106,12 +124,13 @@ pub fn cleanup_game_entities(
     info!("Game entities cleaned up");
 }
 
-//check for restart input -- refresh all data
+//check for restart input -- data was already cleaned up on entering GameOver/Victory,
+//assets are still loaded so go straight back to the main menu
 pub fn toggle_restart(
     input: Res<ButtonInput<KeyCode>>,
     mut next_state: ResMut<NextState<GameState>>,
 ){
     if input.just_pressed(KeyCode::Escape){
-        next_state.set(GameState::Loading);
+        next_state.set(GameState::MainMenu);
     }
 }
@@ -4,6 +4,7 @@ use bevy::prelude::*;
 pub enum GameState{
     #[default]
     Loading,
+    MainMenu,
     Paused,
     Playing,
     Crafting,
@@ -55,4 +56,16 @@ pub enum PauseScreen {
     Main,      // Resume / Settings / Quit
     Settings,  // Audio / Back
     Audio,     // Volume sliders / Back
+}
+
+/// SubState that tracks which screen of the main menu is showing.
+///
+/// Navigation: Main -> RunSetup (New Game) / Settings (Back buttons return to Main)
+#[derive(SubStates, Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[source(GameState = GameState::MainMenu)]
+pub enum MenuScreen {
+    #[default]
+    Main,      // New Game / Continue / Settings / Quit
+    RunSetup,  // Difficulty / Loadout / Start / Back
+    Settings,  // Volume sliders / Back
 }
\ No newline at end of file
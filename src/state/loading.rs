@@ -1,5 +1,6 @@
 use bevy::prelude::*;
-use crate::{audio::GameAudios, game_fonts::GameFonts, state::{GameState, LoadingTimer}};
+use bevy::ecs::system::SystemParam;
+use crate::{audio::GameAudios, cli::CliArgs, game_fonts::GameFonts, state::{GameState, LoadingTimer}};
 
 #[derive(Component)]
 pub struct LoadingScreen;
@@ -54,7 +55,7 @@ pub fn spawn_loading_screen(mut commands: Commands) {
 }
 
 pub fn animate_loading(
-    time: Res<Time>,
+    time: Res<Time<Real>>,
     mut query: Query<&mut Text, With<LoadingText>>,
 ) {
     for mut text in query.iter_mut() {
@@ -63,33 +64,50 @@ pub fn animate_loading(
     }
 }
 
+/// Everything the loading screen waits for
+#[derive(SystemParam)]
+pub struct LoadingAssets<'w> {
+    fonts: Option<Res<'w, GameFonts>>,
+    audios: Option<Res<'w, GameAudios>>,
+    font_assets: Res<'w, Assets<Font>>,
+    audio_assets: Res<'w, Assets<AudioSource>>,
+}
+
+impl LoadingAssets<'_> {
+    fn all_loaded(&self) -> bool {
+        // Resources not inserted yet
+        let (Some(fonts), Some(audios)) = (&self.fonts, &self.audios) else {
+            return false;
+        };
+
+        let fonts_loaded = self.font_assets.get(&fonts.mono).is_some();
+        let audio_loaded = [
+            &audios.player_shoot,
+            &audios.character_hit,
+            &audios.player_hit_explosion,
+            &audios.game_over_music,
+            &audios.victory_music,
+        ].into_iter().all(|handle| self.audio_assets.get(handle).is_some());
+
+        fonts_loaded && audio_loaded
+    }
+}
+
 pub fn check_assets_loaded(
-    time: Res<Time>,
-    fonts: Option<Res<GameFonts>>,
-    audios: Option<Res<GameAudios>>,
-    font_assets: Res<Assets<Font>>,
-    audio_assets: Res<Assets<AudioSource>>,
+    time: Res<Time<Real>>,
+    assets: LoadingAssets,
     mut next_state: ResMut<NextState<GameState>>,
     mut timer: ResMut<LoadingTimer>,
+    cli: Res<CliArgs>,
 ) {
     timer.0.tick(time.delta());
 
-    // Resources not inserted yet
-    let (Some(fonts), Some(audios)) = (fonts, audios) else {
-        return;
-    };
-
-    // Check if all assets have finished loading
-    let fonts_loaded = font_assets.get(&fonts.mono).is_some();
-    let audio_loaded = audio_assets.get(&audios.player_shoot).is_some()
-        && audio_assets.get(&audios.character_hit).is_some()
-        && audio_assets.get(&audios.player_hit_explosion).is_some()
-        && audio_assets.get(&audios.game_over_music).is_some()
-        && audio_assets.get(&audios.victory_music).is_some();
+    // --skip-loading drops the minimum time on the loading screen, assets still have to be in
+    let shown_long_enough = timer.0.is_finished() || cli.skip_loading;
 
-    if fonts_loaded && audio_loaded && timer.0.is_finished() {
-        info!("Assets loaded, transitioning to Playing!");
-        next_state.set(GameState::Playing);
+    if assets.all_loaded() && shown_long_enough {
+        info!("Assets loaded, transitioning to MainMenu!");
+        next_state.set(GameState::MainMenu);
     }
 }
 
@@ -0,0 +1,380 @@
+use bevy::prelude::*;
+use bevy::ecs::hierarchy::ChildSpawnerCommands;
+
+use crate::audio::AudioSettings;
+use crate::difficulty::{Difficulty, DifficultyMultiplier, DifficultyPreset};
+use crate::player::Player;
+use crate::state::{GameState, MenuScreen};
+use super::paused::{VolumeCategory, spawn_volume_row};
+use super::run_setup::{RunSetup, StartingLoadout, cycle_option, start_run};
+
+const BUTTON_COLOR: Color = Color::srgb(0.3, 0.3, 0.3);
+const DISABLED_BUTTON_COLOR: Color = Color::srgb(0.15, 0.15, 0.15);
+
+// =============================================================================
+// MARKER COMPONENTS (for despawning each menu)
+// =============================================================================
+
+#[derive(Component)]
+pub struct MainMenu;
+
+#[derive(Component)]
+pub struct RunSetupMenu;
+
+#[derive(Component)]
+pub struct MenuSettingsMenu;
+
+// =============================================================================
+// BUTTON COMPONENTS (to identify which button was clicked)
+// =============================================================================
+
+#[derive(Component)]
+pub enum MainMenuButton {
+    NewGame,
+    Continue,
+    Settings,
+    Quit,
+}
+
+#[derive(Component)]
+pub enum RunSetupButton {
+    Start,
+    Back,
+}
+
+#[derive(Component)]
+pub enum MenuSettingsButton {
+    Back,
+}
+
+/// Which run setup option a cycle button / value text belongs to
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum RunSetupOption {
+    Difficulty,
+    Loadout,
+    /// Hand tuning a single difficulty multiplier (switches the preset to Custom)
+    Multiplier(DifficultyMultiplier),
+}
+
+/// How much one click on a multiplier row changes it
+const MULTIPLIER_STEP: f32 = 0.25;
+
+/// Button to step an option back (-1) or forward (+1)
+#[derive(Component)]
+pub struct RunSetupCycleButton {
+    pub option: RunSetupOption,
+    pub delta: i32,
+}
+
+/// Text displaying the currently selected value of an option
+#[derive(Component)]
+pub struct RunSetupValueText(pub RunSetupOption);
+
+// =============================================================================
+// MAIN MENU (New Game / Continue / Settings / Quit)
+// =============================================================================
+
+/// Helper to spawn a standard 200x50 menu button
+fn spawn_menu_button(parent: &mut ChildSpawnerCommands, label: &str, button: impl Component, color: Color) {
+    parent.spawn((
+        Button,
+        button,
+        Node {
+            width: Val::Px(200.0),
+            height: Val::Px(50.0),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BackgroundColor(color),
+    )).with_children(|btn| {
+        btn.spawn((
+            Text::new(label),
+            TextFont { font_size: 24.0, ..default() },
+            TextColor(Color::WHITE),
+        ));
+    });
+}
+
+/// Full screen opaque backdrop shared by all main menu screens
+fn menu_root() -> (Node, BackgroundColor) {
+    (
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(20.0),
+            ..default()
+        },
+        BackgroundColor(Color::srgb(0.1, 0.1, 0.15)),
+    )
+}
+
+pub fn spawn_main_menu(mut commands: Commands, players: Query<(), With<Player>>) {
+    // A player only survives into the main menu if the run was left from the pause menu
+    let can_continue = !players.is_empty();
+
+    commands.spawn((MainMenu, menu_root())).with_children(|parent| {
+        // Title
+        parent.spawn((
+            Text::new("DRONE SURVIVAL"),
+            TextFont { font_size: 56.0, ..default() },
+            TextColor(Color::WHITE),
+        ));
+
+        spawn_menu_button(parent, "New Game", MainMenuButton::NewGame, BUTTON_COLOR);
+        spawn_menu_button(
+            parent,
+            "Continue",
+            MainMenuButton::Continue,
+            if can_continue { BUTTON_COLOR } else { DISABLED_BUTTON_COLOR },
+        );
+        spawn_menu_button(parent, "Settings", MainMenuButton::Settings, BUTTON_COLOR);
+        spawn_menu_button(parent, "Quit", MainMenuButton::Quit, BUTTON_COLOR);
+    });
+
+    info!("Main menu spawned");
+}
+
+pub fn despawn_main_menu(mut commands: Commands, query: Query<Entity, With<MainMenu>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+pub fn handle_main_menu_buttons(
+    query: Query<(&Interaction, &MainMenuButton), Changed<Interaction>>,
+    players: Query<(), With<Player>>,
+    mut next_game_state: ResMut<NextState<GameState>>,
+    mut next_menu_screen: ResMut<NextState<MenuScreen>>,
+    mut exit: MessageWriter<AppExit>,
+) {
+    for (interaction, button) in &query {
+        if *interaction == Interaction::Pressed {
+            match button {
+                MainMenuButton::NewGame => { next_menu_screen.set(MenuScreen::RunSetup); }
+                MainMenuButton::Continue => {
+                    if !players.is_empty() {
+                        next_game_state.set(GameState::Playing);
+                    }
+                }
+                MainMenuButton::Settings => { next_menu_screen.set(MenuScreen::Settings); }
+                MainMenuButton::Quit => { exit.write(AppExit::Success); }
+            }
+        }
+    }
+}
+
+// =============================================================================
+// RUN SETUP (Difficulty / Loadout / Start / Back)
+// =============================================================================
+
+pub fn spawn_run_setup_menu(mut commands: Commands, setup: Res<RunSetup>) {
+    commands.spawn((RunSetupMenu, menu_root())).with_children(|parent| {
+        // Title
+        parent.spawn((
+            Text::new("NEW RUN"),
+            TextFont { font_size: 48.0, ..default() },
+            TextColor(Color::WHITE),
+        ));
+
+        spawn_option_row(parent, "Difficulty", RunSetupOption::Difficulty, &setup);
+        for multiplier in DifficultyMultiplier::ALL {
+            spawn_option_row(parent, multiplier.label(), RunSetupOption::Multiplier(multiplier), &setup);
+        }
+        spawn_option_row(parent, "Loadout", RunSetupOption::Loadout, &setup);
+
+        let seed_label = match setup.seed {
+            Some(seed) => format!("Seed: {} (--seed)", seed),
+            None => "Seed: random".to_string(),
+        };
+        parent.spawn((
+            Text::new(seed_label),
+            TextFont { font_size: 16.0, ..default() },
+            TextColor(Color::srgb(0.6, 0.6, 0.6)),
+        ));
+
+        spawn_menu_button(parent, "Start", RunSetupButton::Start, Color::srgb(0.2, 0.5, 0.2));
+        spawn_menu_button(parent, "Back", RunSetupButton::Back, BUTTON_COLOR);
+    });
+}
+
+/// Text and color shown for an option's current value
+fn option_value(setup: &RunSetup, option: RunSetupOption) -> (String, Color) {
+    match option {
+        RunSetupOption::Difficulty => (
+            setup.difficulty.preset.name().to_string(),
+            setup.difficulty.preset.color(),
+        ),
+        RunSetupOption::Loadout => (
+            format!("{} ({})", setup.loadout.name(), setup.loadout.description()),
+            Color::WHITE,
+        ),
+        RunSetupOption::Multiplier(multiplier) => (
+            format!("x{:.2}", setup.difficulty.multiplier(multiplier)),
+            Color::srgb(0.8, 0.8, 0.8),
+        ),
+    }
+}
+
+/// Helper to spawn an option row: Label [ < ] Value [ > ]
+fn spawn_option_row(
+    parent: &mut ChildSpawnerCommands,
+    label: &str,
+    option: RunSetupOption,
+    setup: &RunSetup,
+) {
+    let (initial_value, initial_color) = option_value(setup, option);
+
+    parent.spawn((
+        Node {
+            flex_direction: FlexDirection::Row,
+            align_items: AlignItems::Center,
+            column_gap: Val::Px(10.0),
+            ..default()
+        },
+    )).with_children(|row: &mut ChildSpawnerCommands| {
+        // Label
+        row.spawn((
+            Text::new(label),
+            TextFont { font_size: 20.0, ..default() },
+            TextColor(Color::WHITE),
+            Node {
+                width: Val::Px(120.0),
+                ..default()
+            },
+        ));
+
+        for (delta, glyph) in [(-1, "<"), (1, ">")] {
+            if delta > 0 {
+                // Value text sits between the two arrows
+                row.spawn((
+                    Text::new(initial_value.clone()),
+                    TextFont { font_size: 20.0, ..default() },
+                    TextColor(initial_color),
+                    RunSetupValueText(option),
+                    Node {
+                        width: Val::Px(260.0),
+                        justify_content: JustifyContent::Center,
+                        ..default()
+                    },
+                    TextLayout::new_with_justify(Justify::Center),
+                ));
+            }
+
+            row.spawn((
+                Button,
+                RunSetupCycleButton { option, delta },
+                Node {
+                    width: Val::Px(40.0),
+                    height: Val::Px(40.0),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                BackgroundColor(BUTTON_COLOR),
+            )).with_children(|btn: &mut ChildSpawnerCommands| {
+                btn.spawn((
+                    Text::new(glyph),
+                    TextFont { font_size: 24.0, ..default() },
+                    TextColor(Color::WHITE),
+                ));
+            });
+        }
+    });
+}
+
+pub fn despawn_run_setup_menu(mut commands: Commands, query: Query<Entity, With<RunSetupMenu>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+pub fn handle_run_setup_buttons(
+    mut commands: Commands,
+    query: Query<(&Interaction, &RunSetupButton), Changed<Interaction>>,
+    mut next_menu_screen: ResMut<NextState<MenuScreen>>,
+) {
+    for (interaction, button) in &query {
+        if *interaction == Interaction::Pressed {
+            match button {
+                RunSetupButton::Start => { commands.run_system_cached(start_run); }
+                RunSetupButton::Back => { next_menu_screen.set(MenuScreen::Main); }
+            }
+        }
+    }
+}
+
+pub fn handle_run_setup_cycle_buttons(
+    query: Query<(&Interaction, &RunSetupCycleButton), Changed<Interaction>>,
+    mut setup: ResMut<RunSetup>,
+    mut text_query: Query<(&mut Text, &mut TextColor, &RunSetupValueText)>,
+) {
+    for (interaction, cycle) in &query {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        match cycle.option {
+            RunSetupOption::Difficulty => {
+                let preset = cycle_option(&DifficultyPreset::ALL, setup.difficulty.preset, cycle.delta);
+                setup.difficulty = Difficulty::from_preset(preset);
+            }
+            RunSetupOption::Loadout => {
+                setup.loadout = cycle_option(&StartingLoadout::ALL, setup.loadout, cycle.delta);
+            }
+            RunSetupOption::Multiplier(multiplier) => {
+                setup.difficulty.adjust(multiplier, cycle.delta as f32 * MULTIPLIER_STEP);
+            }
+        }
+
+        // Update the display text (a preset change touches every multiplier row)
+        for (mut text, mut color, value_text) in &mut text_query {
+            let (value, value_color) = option_value(&setup, value_text.0);
+            **text = value;
+            *color = TextColor(value_color);
+        }
+    }
+}
+
+// =============================================================================
+// SETTINGS (Volume sliders / Back) - same controls as the pause menu's audio screen
+// =============================================================================
+
+pub fn spawn_menu_settings(mut commands: Commands, audio_settings: Res<AudioSettings>) {
+    commands.spawn((MenuSettingsMenu, menu_root())).with_children(|parent| {
+        // Title
+        parent.spawn((
+            Text::new("SETTINGS"),
+            TextFont { font_size: 48.0, ..default() },
+            TextColor(Color::WHITE),
+        ));
+
+        spawn_volume_row(parent, "Master", VolumeCategory::Master, audio_settings.master as i32);
+        spawn_volume_row(parent, "SFX", VolumeCategory::Sfx, audio_settings.sfx as i32);
+        spawn_volume_row(parent, "Music", VolumeCategory::Music, audio_settings.music as i32);
+
+        spawn_menu_button(parent, "Back", MenuSettingsButton::Back, BUTTON_COLOR);
+    });
+}
+
+pub fn despawn_menu_settings(mut commands: Commands, query: Query<Entity, With<MenuSettingsMenu>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+pub fn handle_menu_settings_buttons(
+    query: Query<(&Interaction, &MenuSettingsButton), Changed<Interaction>>,
+    mut next_menu_screen: ResMut<NextState<MenuScreen>>,
+) {
+    for (interaction, button) in &query {
+        if *interaction == Interaction::Pressed {
+            match button {
+                MenuSettingsButton::Back => { next_menu_screen.set(MenuScreen::Main); }
+            }
+        }
+    }
+}
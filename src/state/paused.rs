@@ -1,7 +1,7 @@
 use bevy::prelude::*;
 use bevy::ecs::hierarchy::ChildSpawnerCommands;
 
-use crate::{audio::AudioSettings, state::{GameState, PauseScreen}};
+use crate::{audio::AudioSettings, combat::HitFeedbackSettings, replay::ReplayButton, state::{GameState, PauseScreen}};
 
 // =============================================================================
 // MARKER COMPONENTS (for despawning each menu)
@@ -24,15 +24,21 @@ pub struct AudioMenu;
 pub enum PauseButton {
     Resume,
     Settings,
+    MainMenu,
     Quit,
 }
 
 #[derive(Component)]
 pub enum SettingsButton {
     Audio,
+    ScreenShake,
     Back,
 }
 
+/// Label of the screen shake toggle
+#[derive(Component)]
+pub struct ScreenShakeText;
+
 #[derive(Component)]
 pub enum AudioButton {
     Back,
@@ -61,7 +67,7 @@ pub struct VolumeAdjustButton {
 pub struct VolumeValueText(pub VolumeCategory);
 
 // =============================================================================
-// MAIN PAUSE MENU (Resume / Settings / Quit)
+// MAIN PAUSE MENU (Resume / Settings / Main Menu / Quit)
 // =============================================================================
 
 pub fn spawn_pause_menu(mut commands: Commands) {
@@ -89,6 +95,7 @@ pub fn spawn_pause_menu(mut commands: Commands) {
         parent.spawn((
             Button,
             PauseButton::Resume,
+            ReplayButton("resume".to_string()),
             Node {
                 width: Val::Px(200.0),
                 height: Val::Px(50.0),
@@ -125,6 +132,26 @@ pub fn spawn_pause_menu(mut commands: Commands) {
             ));
         });
 
+        // Main menu button (leaves the run suspended so it can be continued)
+        parent.spawn((
+            Button,
+            PauseButton::MainMenu,
+            Node {
+                width: Val::Px(200.0),
+                height: Val::Px(50.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.3, 0.3, 0.3)),
+        )).with_children(|btn| {
+            btn.spawn((
+                Text::new("Main Menu"),
+                TextFont { font_size: 24.0, ..default() },
+                TextColor(Color::WHITE),
+            ));
+        });
+
         // Quit button
         parent.spawn((
             Button,
@@ -164,6 +191,7 @@ pub fn handle_pause_buttons(
             match button {
                 PauseButton::Resume => { next_game_state.set(GameState::Playing); }
                 PauseButton::Settings => { next_pause_screen.set(PauseScreen::Settings); }
+                PauseButton::MainMenu => { next_game_state.set(GameState::MainMenu); }
                 PauseButton::Quit => { exit.write(AppExit::Success); }
             }
         }
@@ -171,10 +199,10 @@ pub fn handle_pause_buttons(
 }
 
 // =============================================================================
-// SETTINGS MENU (Audio / Back)
+// SETTINGS MENU (Audio / Screen Shake / Back)
 // =============================================================================
 
-pub fn spawn_settings_menu(mut commands: Commands) {
+pub fn spawn_settings_menu(mut commands: Commands, hit_feedback: Res<HitFeedbackSettings>) {
     commands.spawn((
         SettingsMenu,
         Node {
@@ -215,6 +243,27 @@ pub fn spawn_settings_menu(mut commands: Commands) {
             ));
         });
 
+        // Screen shake toggle
+        parent.spawn((
+            Button,
+            SettingsButton::ScreenShake,
+            Node {
+                width: Val::Px(200.0),
+                height: Val::Px(50.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.3, 0.3, 0.3)),
+        )).with_children(|btn| {
+            btn.spawn((
+                Text::new(screen_shake_label(hit_feedback.screen_shake)),
+                TextFont { font_size: 24.0, ..default() },
+                TextColor(Color::WHITE),
+                ScreenShakeText,
+            ));
+        });
+
         // Back button
         parent.spawn((
             Button,
@@ -243,14 +292,26 @@ pub fn despawn_settings_menu(mut commands: Commands, query: Query<Entity, With<S
     }
 }
 
+fn screen_shake_label(on: bool) -> &'static str {
+    if on { "Screen Shake: On" } else { "Screen Shake: Off" }
+}
+
 pub fn handle_settings_buttons(
     query: Query<(&Interaction, &SettingsButton), Changed<Interaction>>,
     mut next_pause_screen: ResMut<NextState<PauseScreen>>,
+    mut hit_feedback: ResMut<HitFeedbackSettings>,
+    mut shake_text: Query<&mut Text, With<ScreenShakeText>>,
 ) {
     for (interaction, button) in &query {
         if *interaction == Interaction::Pressed {
             match button {
                 SettingsButton::Audio => { next_pause_screen.set(PauseScreen::Audio); }
+                SettingsButton::ScreenShake => {
+                    hit_feedback.screen_shake = !hit_feedback.screen_shake;
+                    for mut text in &mut shake_text {
+                        **text = screen_shake_label(hit_feedback.screen_shake).to_string();
+                    }
+                }
                 SettingsButton::Back => { next_pause_screen.set(PauseScreen::Main); }
             }
         }
@@ -314,7 +375,7 @@ pub fn spawn_audio_menu(mut commands: Commands, audio_settings: Res<AudioSetting
 }
 
 /// Helper to spawn a volume control row: Label [ - ] 50% [ + ]
-fn spawn_volume_row(
+pub fn spawn_volume_row(
     parent: &mut ChildSpawnerCommands,
     label: &str,
     category: VolumeCategory,
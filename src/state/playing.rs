@@ -1,22 +1,33 @@
 use bevy::prelude::*;
-use crate::building::PlaceableType;
-use crate::inventory::{Inventory, add_placeable};
-use crate::physics::CircleHitBox;
+use crate::cli::CliArgs;
+use crate::inventory::{Inventory, give_item};
+use crate::physics::{Collider, InterpolatedTransform};
 use crate::player::Player;
+use crate::player::abilities::Abilities;
+use crate::player::buffs::ActiveBuffs;
 use crate::ascii_sprite::AsciiSprite;
-use crate::combat::{Health, HealthBar, Weapon, WeaponType};
+use crate::combat::{GodMode, Health, HealthBar, HitReaction, Regeneration, WeaponDefinitions};
+use crate::difficulty::Difficulty;
+use super::run_setup::RunSetup;
 
 pub fn spawn_player(
     mut commands: Commands,
-    players: Query<Entity, With<Player>>
+    players: Query<Entity, With<Player>>,
+    setup: Res<RunSetup>,
+    difficulty: Res<Difficulty>,
+    cli: Res<CliArgs>,
+    weapons: Res<WeaponDefinitions>,
 ) {
     if players.count() == 0{
-        let mut inventory = Inventory::default();
-        // TODO: remove test items
-        add_placeable(&mut inventory, PlaceableType::ExtractionBeacon, 1);
-        add_placeable(&mut inventory, PlaceableType::Wall, 4);
+        let mut inventory = Inventory::new(&weapons);
+        setup.loadout.apply(&mut inventory, &weapons);
+        // --give
+        for (item, count) in &cli.give {
+            give_item(&mut inventory, &weapons, *item, *count);
+        }
+        info!("Starting run: {} difficulty, {} loadout", difficulty.preset.name(), setup.loadout.name());
 
-        commands.spawn((
+        let mut player = commands.spawn((
             Transform::from_xyz(0.0, 0.0, 0.0),
             AsciiSprite {
                 glyph: "@".to_string(),
@@ -25,12 +36,23 @@ pub fn spawn_player(
                 bg_color: None
             },
             Player,
-            Weapon::from_type(WeaponType::Pistol),
-            CircleHitBox { radius: 10.0 },
-            Health::new(10),
+            InterpolatedTransform::default(), // moved in FixedUpdate without a Velocity
+            Collider::circle(10.0),
+            Health::new(difficulty.scale_player_health(10)),
             HealthBar { max_width: 32.0, offset: 24.0 },
             inventory,
+            Abilities::default(),
+            ActiveBuffs::default(),
+            HitReaction,
         ));
+
+        if difficulty.player_regen {
+            player.insert(Regeneration::default());
+        }
+        if cli.god_mode {
+            player.insert(GodMode);
+            info!("God mode on");
+        }
     }
 
 }
\ No newline at end of file
@@ -0,0 +1,121 @@
+use bevy::prelude::*;
+
+use crate::game_fonts::GameFonts;
+use crate::run_history::{RunHistory, RunRecord};
+
+const TOP_RUNS: usize = 10;
+const RECENT_RUNS: usize = 5;
+const HEADER_COLOR: Color = Color::srgb(1.0, 1.0, 0.0);
+
+// =============================================================================
+// MARKER COMPONENTS
+// =============================================================================
+
+/// Leaderboard overlay on top of the game over / victory screen, toggled with H
+#[derive(Component)]
+pub struct RunHistoryView;
+
+// =============================================================================
+// SPAWN / DESPAWN
+// =============================================================================
+
+fn record_row(rank: Option<usize>, record: &RunRecord) -> String {
+    let rank = rank.map(|r| format!("{:>2}.", r)).unwrap_or_else(|| "   ".to_string());
+    let secs = record.duration_secs as u32;
+    format!(
+        "{} {:>7}  wave {:>2}  {:02}:{:02}  {:<11} {:<7} {}",
+        rank,
+        record.score,
+        record.waves_reached,
+        secs / 60,
+        secs % 60,
+        record.outcome.name(),
+        record.difficulty,
+        record.date(),
+    )
+}
+
+fn spawn_history_view(commands: &mut Commands, history: &RunHistory, fonts: &GameFonts) {
+    let font = fonts.mono.clone();
+    commands.spawn((
+        RunHistoryView,
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            row_gap: Val::Px(4.0),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.9)),
+        GlobalZIndex(10),
+    )).with_children(|parent| {
+        let header = |text: &str| (
+            Text::new(text),
+            TextFont { font: font.clone(), font_size: 24.0, ..default() },
+            TextColor(HEADER_COLOR),
+        );
+        let row = |text: String, color: Color| (
+            Text::new(text),
+            TextFont { font: font.clone(), font_size: 16.0, ..default() },
+            TextColor(color),
+        );
+
+        parent.spawn(header("TOP RUNS"));
+        if history.records.is_empty() {
+            parent.spawn(row("No runs recorded yet".to_string(), Color::WHITE));
+        }
+        let latest = history.latest();
+        for (i, record) in history.top(TOP_RUNS).into_iter().enumerate() {
+            // highlight the run that just finished
+            let color = if latest.is_some_and(|latest| std::ptr::eq(latest, record)) {
+                HEADER_COLOR
+            } else {
+                Color::WHITE
+            };
+            parent.spawn(row(record_row(Some(i + 1), record), color));
+        }
+
+        parent.spawn((header("RECENT"), Node { margin: UiRect::top(Val::Px(16.0)), ..default() }));
+        for record in history.recent(RECENT_RUNS) {
+            parent.spawn(row(record_row(None, record), Color::srgb(0.8, 0.8, 0.8)));
+        }
+
+        parent.spawn((
+            row("Press H to close".to_string(), Color::WHITE),
+            Node { margin: UiRect::top(Val::Px(16.0)), ..default() },
+        ));
+    });
+}
+
+pub fn despawn_run_history_view(mut commands: Commands, query: Query<Entity, With<RunHistoryView>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+// =============================================================================
+// UPDATE
+// =============================================================================
+
+pub fn toggle_run_history_view(
+    mut commands: Commands,
+    input: Res<ButtonInput<KeyCode>>,
+    history: Res<RunHistory>,
+    fonts: Res<GameFonts>,
+    query: Query<Entity, With<RunHistoryView>>,
+) {
+    if !input.just_pressed(KeyCode::KeyH) {
+        return;
+    }
+
+    if query.is_empty() {
+        spawn_history_view(&mut commands, &history, &fonts);
+    } else {
+        for entity in &query {
+            commands.entity(entity).despawn();
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use bevy::prelude::*;
+
+use crate::building::PlaceableType;
+use crate::cli::CliArgs;
+use crate::combat::{WeaponDefinitions, WeaponId};
+use crate::difficulty::Difficulty;
+use crate::game_rng::{GameRng, random_seed};
+use crate::inventory::{Inventory, add_placeable, add_weapon};
+use crate::run_stats::RunStats;
+use crate::spawning::{WaveDefinitions, WaveState};
+use super::game_over::cleanup_game_entities;
+use super::GameState;
+
+/// What the player starts the run with, picked on the run setup screen
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum StartingLoadout {
+    #[default]
+    Standard,
+    Gunner,
+    Engineer,
+}
+
+impl StartingLoadout {
+    pub const ALL: [StartingLoadout; 3] = [
+        StartingLoadout::Standard,
+        StartingLoadout::Gunner,
+        StartingLoadout::Engineer,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            StartingLoadout::Standard => "Standard",
+            StartingLoadout::Gunner => "Gunner",
+            StartingLoadout::Engineer => "Engineer",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|loadout| loadout.name() == name)
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            StartingLoadout::Standard => "Pistol",
+            StartingLoadout::Gunner => "Pistol + Shotgun",
+            StartingLoadout::Engineer => "Pistol + 6 Walls",
+        }
+    }
+
+    /// Adds the loadout's items on top of the default inventory
+    pub fn apply(&self, inventory: &mut Inventory, weapons: &WeaponDefinitions) {
+        match self {
+            StartingLoadout::Standard => {}
+            StartingLoadout::Gunner => {
+                add_weapon(inventory, weapons, WeaponId::SHOTGUN);
+            }
+            StartingLoadout::Engineer => {
+                add_placeable(inventory, PlaceableType::Wall, 6);
+            }
+        }
+    }
+}
+
+/// Choices made on the run setup screen, read when the player is spawned
+#[derive(Resource, Default)]
+pub struct RunSetup {
+    /// Copied into the Difficulty resource when the run starts
+    pub difficulty: Difficulty,
+    pub loadout: StartingLoadout,
+    /// Fixed seed from the command line, otherwise every run rolls a new one
+    pub seed: Option<u64>,
+}
+
+/// Sent when a fresh run starts (not when a paused run is continued)
+#[derive(Message)]
+pub struct RunStarted;
+
+/// Starts a fresh run from the current RunSetup. Run with `commands.run_system_cached`
+pub fn start_run(
+    mut commands: Commands,
+    setup: Res<RunSetup>,
+    mut difficulty: ResMut<Difficulty>,
+    mut stats: ResMut<RunStats>,
+    mut rng: ResMut<GameRng>,
+    mut next_game_state: ResMut<NextState<GameState>>,
+    mut started_messages: MessageWriter<RunStarted>,
+) {
+    // Throw away a run left from the pause menu before starting fresh
+    commands.run_system_cached(cleanup_game_entities);
+    commands.run_system_cached(apply_start_wave);
+    *difficulty = setup.difficulty;
+    *stats = RunStats::default();
+    rng.reseed(setup.seed.unwrap_or_else(random_seed));
+    next_game_state.set(GameState::Playing);
+    started_messages.write(RunStarted);
+}
+
+/// --wave: jump straight to a later wave. Runs after cleanup_game_entities resets WaveState
+fn apply_start_wave(
+    cli: Res<CliArgs>,
+    wave_defs: Res<WaveDefinitions>,
+    mut wave_state: ResMut<WaveState>,
+) {
+    if let Some(wave) = cli.start_wave {
+        wave_state.wave_number = (wave - 1).min(wave_defs.waves.len().saturating_sub(1));
+        info!("Starting at wave {}", wave_state.wave_number + 1);
+    }
+}
+
+pub fn apply_cli_seed(cli: Res<CliArgs>, mut setup: ResMut<RunSetup>) {
+    setup.seed = cli.seed;
+}
+
+/// Steps through `options` by `delta`, wrapping around
+pub fn cycle_option<T: Copy + PartialEq>(options: &[T], current: T, delta: i32) -> T {
+    let len = options.len() as i32;
+    let index = options.iter().position(|o| *o == current).unwrap_or(0) as i32;
+    options[(index + delta).rem_euclid(len) as usize]
+}
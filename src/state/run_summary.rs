@@ -0,0 +1,115 @@
+use bevy::prelude::*;
+use bevy::ecs::hierarchy::ChildSpawnerCommands;
+
+use crate::combat::WeaponDefinitions;
+use crate::run_history::RunHistory;
+use crate::run_stats::RunStats;
+
+const HEADER_COLOR: Color = Color::srgb(1.0, 1.0, 0.0);
+const LINE_COLOR: Color = Color::srgb(0.85, 0.85, 0.85);
+
+/// "mm:ss"
+fn format_time(secs: f32) -> String {
+    let secs = secs as u32;
+    format!("{:02}:{:02}", secs / 60, secs % 60)
+}
+
+/// Sorted by name so the screen doesn't reshuffle between runs
+fn sorted_by_name<'a, K, V: Copy>(map: &std::collections::HashMap<K, V>, name: impl Fn(&K) -> &'a str) -> Vec<(&'a str, V)> {
+    let mut entries: Vec<_> = map.iter().map(|(key, value)| (name(key), *value)).collect();
+    entries.sort_by_key(|(name, _)| *name);
+    entries
+}
+
+fn spawn_section(parent: &mut ChildSpawnerCommands, title: &str, lines: Vec<String>) {
+    parent.spawn(Node {
+        flex_direction: FlexDirection::Column,
+        align_items: AlignItems::Start,
+        row_gap: Val::Px(2.0),
+        ..default()
+    }).with_children(|section| {
+        section.spawn((
+            Text::new(title),
+            TextFont { font_size: 20.0, ..default() },
+            TextColor(HEADER_COLOR),
+        ));
+        for line in lines {
+            section.spawn((
+                Text::new(line),
+                TextFont { font_size: 16.0, ..default() },
+                TextColor(LINE_COLOR),
+            ));
+        }
+    });
+}
+
+/// Stats panel shared by the game over and victory screens
+pub fn spawn_run_summary(parent: &mut ChildSpawnerCommands, stats: &RunStats, weapons: &WeaponDefinitions, seed: u64) {
+    parent.spawn((
+        Node {
+            flex_direction: FlexDirection::Row,
+            column_gap: Val::Px(40.0),
+            padding: UiRect::all(Val::Px(20.0)),
+            margin: UiRect::vertical(Val::Px(20.0)),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.9)),
+    )).with_children(|panel| {
+        spawn_section(panel, "RUN", vec![
+            format!("Time survived: {}", format_time(stats.time_survived.elapsed_secs())),
+            format!("Waves cleared: {}", stats.waves_cleared),
+            format!("Waves overrun: {}", stats.waves_overrun),
+            format!("Damage dealt: {}", stats.damage_dealt),
+            format!("Damage taken: {}", stats.damage_taken),
+            format!("Structures built: {}", stats.structures_built),
+            format!("Structures lost: {}", stats.structures_lost),
+            format!("Seed: {}", seed),
+        ]);
+
+        let mut kills = vec![format!("Total: {}", stats.total_kills())];
+        kills.extend(sorted_by_name(&stats.kills, |kind| kind.name())
+            .into_iter()
+            .map(|(name, count)| format!("{}: {}", name, count)));
+        spawn_section(panel, "DRONES DESTROYED", kills);
+
+        let mut shots: Vec<String> = sorted_by_name(&stats.shots, |weapon| weapons.name(*weapon))
+            .into_iter()
+            .map(|(name, shot)| format!("{}: {}/{} ({:.0}%)", name, shot.hit, shot.fired, shot.accuracy() * 100.0))
+            .collect();
+        if shots.is_empty() {
+            shots.push("No shots fired".to_string());
+        }
+        spawn_section(panel, "SHOTS HIT", shots);
+
+        let mut resources = vec![format!("Total: {}", stats.total_resources())];
+        resources.extend(sorted_by_name(&stats.resources_collected, |resource| resource.name())
+            .into_iter()
+            .map(|(name, count)| format!("{}: {}", name, count)));
+        spawn_section(panel, "RESOURCES", resources);
+    });
+}
+
+/// Score of the run that just ended plus the key hints, under the summary
+pub fn spawn_end_footer(parent: &mut ChildSpawnerCommands, history: &RunHistory) {
+    if let Some(unranked) = &history.unranked {
+        parent.spawn((
+            Text::new(format!("Score: {}  (cheats or replay, not recorded)", unranked.score)),
+            TextFont { font_size: 28.0, ..default() },
+            TextColor(HEADER_COLOR),
+        ));
+    } else if let Some(latest) = history.latest() {
+        let best = if history.latest_is_best() { "  NEW BEST!" } else { "" };
+        parent.spawn((
+            Text::new(format!("Score: {}{}", latest.score, best)),
+            TextFont { font_size: 28.0, ..default() },
+            TextColor(HEADER_COLOR),
+        ));
+    }
+
+    parent.spawn((
+        Text::new("Press H to view run history\nPress ESC to return to the main menu"),
+        TextFont { font_size: 24.0, ..default() },
+        TextColor(Color::WHITE),
+        TextLayout::new_with_justify(Justify::Center),
+    ));
+}
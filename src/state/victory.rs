@@ -1,15 +1,21 @@
 use bevy::prelude::*;
 use crate::audio::{AudioSettings, MusicTrack, play_music};
+use crate::combat::WeaponDefinitions;
+use crate::run_stats::RunStats;
+use crate::game_rng::GameRng;
+use crate::run_history::RunHistory;
+use super::run_summary::{spawn_end_footer, spawn_run_summary};
 
 #[derive(Component)]
 pub struct VictoryMenu;
 
-pub fn spawn_victory_menu(mut commands: Commands) {
+pub fn spawn_victory_menu(mut commands: Commands, stats: Res<RunStats>, weapons: Res<WeaponDefinitions>, history: Res<RunHistory>, rng: Res<GameRng>) {
     commands.spawn((
         VictoryMenu,
         Node {
             width: Val::Percent(100.0),
             height: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
             justify_content: JustifyContent::Center,
             align_items: AlignItems::Center,
             ..default()
@@ -17,7 +23,7 @@ pub fn spawn_victory_menu(mut commands: Commands) {
         BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
     )).with_children(|parent| {
         parent.spawn((
-            Text::new("Victory Over the Clankers!\n\nPress ESC to restart"),
+            Text::new("Victory Over the Clankers!"),
             TextFont {
                 font_size: 36.0,
                 ..default()
@@ -25,6 +31,10 @@ pub fn spawn_victory_menu(mut commands: Commands) {
             TextColor(Color::WHITE),
             TextLayout::new_with_justify(Justify::Center),
         ));
+
+        spawn_run_summary(parent, &stats, &weapons, rng.seed());
+
+        spawn_end_footer(parent, &history);
     });
     
     info!("Victory menu spawned");
@@ -0,0 +1,119 @@
+use bevy::prelude::*;
+
+use crate::state::{GameState, InGame};
+
+/// Debug hotkeys: press once to switch to that speed, again to go back to 1x
+const DEBUG_SPEEDS: [(KeyCode, f32); 3] = [
+    (KeyCode::F5, 0.25),
+    (KeyCode::F6, 2.0),
+    (KeyCode::F7, 4.0),
+];
+
+/// Debug hotkey that fires a bullet-time effect, for testing the power-up hook
+const DEBUG_BULLET_TIME_KEY: KeyCode = KeyCode::F8;
+
+/// Never let stacked effects stop time completely, that's what pausing is for
+const MIN_SCALE: f32 = 0.05;
+
+/// Ask for a temporary slow-down (or speed-up), e.g. from a bullet-time power-up.
+/// Overlapping requests multiply together.
+#[derive(Message)]
+pub struct BulletTimeEvent {
+    pub scale: f32,
+    /// Real seconds, so a 0.25x slow-mo for 2s lasts 2s on the wall clock
+    pub duration_secs: f32,
+}
+
+struct TimeScaleEffect {
+    scale: f32,
+    timer: Timer,
+}
+
+/// How fast game time runs compared to real time. Applied to `Time<Virtual>`, so everything
+/// reading `Res<Time>` (movement, FixedUpdate, timers, spawning, beacon charge) follows it.
+/// UI that should keep real speed reads `Res<Time<Real>>` instead.
+#[derive(Resource)]
+pub struct TimeScale {
+    /// Set by the debug hotkeys
+    pub debug: f32,
+    effects: Vec<TimeScaleEffect>,
+}
+
+impl Default for TimeScale {
+    fn default() -> Self {
+        Self { debug: 1.0, effects: Vec::new() }
+    }
+}
+
+impl TimeScale {
+    pub fn current(&self) -> f32 {
+        let effects: f32 = self.effects.iter().map(|effect| effect.scale).product();
+        (self.debug * effects).max(MIN_SCALE)
+    }
+}
+
+fn debug_time_scale_hotkeys(
+    input: Res<ButtonInput<KeyCode>>,
+    mut time_scale: ResMut<TimeScale>,
+    mut bullet_time: MessageWriter<BulletTimeEvent>,
+) {
+    for (key, speed) in DEBUG_SPEEDS {
+        if input.just_pressed(key) {
+            time_scale.debug = if time_scale.debug == speed { 1.0 } else { speed };
+            info!("Time scale: x{}", time_scale.debug);
+        }
+    }
+
+    if input.just_pressed(DEBUG_BULLET_TIME_KEY) {
+        bullet_time.write(BulletTimeEvent { scale: 0.3, duration_secs: 3.0 });
+    }
+}
+
+fn start_bullet_time(mut messages: MessageReader<BulletTimeEvent>, mut time_scale: ResMut<TimeScale>) {
+    for event in messages.read() {
+        time_scale.effects.push(TimeScaleEffect {
+            scale: event.scale,
+            timer: Timer::from_seconds(event.duration_secs, TimerMode::Once),
+        });
+    }
+}
+
+/// Real time, so a slow-down lasts as long as it says. Paused / crafting time doesn't count
+fn tick_time_scale_effects(real_time: Res<Time<Real>>, mut time_scale: ResMut<TimeScale>) {
+    for effect in &mut time_scale.effects {
+        effect.timer.tick(real_time.delta());
+    }
+    time_scale.effects.retain(|effect| !effect.timer.is_finished());
+}
+
+fn apply_time_scale(time_scale: Res<TimeScale>, mut virtual_time: ResMut<Time<Virtual>>) {
+    let scale = time_scale.current();
+    if virtual_time.relative_speed() != scale {
+        virtual_time.set_relative_speed(scale);
+    }
+}
+
+/// Effects and the debug speed don't carry over to the next run
+fn reset_time_scale(mut time_scale: ResMut<TimeScale>) {
+    *time_scale = TimeScale::default();
+}
+
+pub struct TimeScalePlugin;
+
+impl Plugin for TimeScalePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<TimeScale>()
+            .add_message::<BulletTimeEvent>()
+            .add_systems(Update, (
+                start_bullet_time,
+                tick_time_scale_effects.run_if(in_state(GameState::Playing)),
+                apply_time_scale,
+            ).chain())
+            .add_systems(OnExit(InGame::Yes), reset_time_scale);
+
+        if cfg!(debug_assertions) {
+            app.add_systems(Update, debug_time_scale_hotkeys.before(start_bullet_time));
+        }
+    }
+}